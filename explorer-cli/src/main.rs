@@ -0,0 +1,620 @@
+mod output;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use explorer_common::{
+    builder::{parse_args, InvocationTx},
+    contract_id::{self, FUTURENET_PASSPHRASE},
+    operations::{
+        get_account_transactions, get_contract_operations, get_operations, get_operations_in_range,
+    },
+    paging::Range,
+    scval,
+    signatures::check_signatures,
+    spec, strkey, testgen,
+    transactions::{get_transaction, process_tx_operations},
+    types::common::{Event, Processed},
+    verify,
+};
+use serde::Serialize;
+use stellar_xdr::{
+    ContractEvent, ContractEventBody, LedgerFootprint, ReadXdr, ScSpecEntry, ScSpecTypeDef,
+    ScSpecTypeUdt, ScVal, TransactionEnvelope, TransactionMeta, TransactionResult, WriteXdr,
+};
+
+use output::{print_records, print_tx, print_value, print_verification, Format, PROCESSED_HEADER};
+
+const DEFAULT_HORIZON: &str = "https://horizon-futurenet.stellar.org";
+
+#[derive(Parser)]
+#[command(
+    name = "soroban-explorer",
+    version,
+    about = "Explore Soroban from the terminal"
+)]
+struct Cli {
+    /// Horizon instance to query.
+    #[arg(long, env = "HORIZON_URL", default_value = DEFAULT_HORIZON, global = true)]
+    horizon: String,
+    /// Passphrase of the network Horizon serves, to derive contract ids and check
+    /// signatures.
+    #[arg(long, env = "NETWORK_PASSPHRASE", default_value = FUTURENET_PASSPHRASE, global = true)]
+    network_passphrase: String,
+    #[arg(long, short, value_enum, default_value_t = Format::Table, global = true)]
+    output: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Decode a transaction.
+    Tx {
+        hash: String,
+        /// Also recompute the transaction hash and check its signatures.
+        #[arg(long)]
+        verify: bool,
+        /// Wasm of the invoked contract, to name the contract errors from its spec.
+        #[arg(long)]
+        wasm: Option<PathBuf>,
+    },
+    /// Print a soroban-sdk test replaying the invocations of a transaction.
+    TestCase { hash: String },
+    /// Explore a contract.
+    Contract {
+        id: String,
+        #[command(subcommand)]
+        command: ContractCommand,
+    },
+    /// List the Soroban operations submitted by an account.
+    Account {
+        id: String,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        #[arg(long, default_value_t = 10)]
+        max_pages: usize,
+    },
+    /// List the contract events emitted by the latest invocations.
+    Events {
+        /// Only show events emitted by this contract.
+        #[arg(long)]
+        contract: Option<String>,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        #[arg(long, default_value_t = 10)]
+        max_pages: usize,
+    },
+    /// Decode a base64 XDR value to JSON.
+    DecodeXdr {
+        #[arg(long = "type", value_enum)]
+        ty: XdrType,
+        xdr: String,
+    },
+    /// Encode a value as base64 `ScVal` XDR, e.g. `u64:5`, `sym:transfer`, `bytes:0xdead` or
+    /// `{"to": "G...", "amount": "i128:1000"}`.
+    EncodeScVal {
+        value: String,
+        /// Contract wasm whose spec defines `--type`.
+        #[arg(long, requires = "ty")]
+        wasm: Option<PathBuf>,
+        /// Type of the contract spec to parse the value as.
+        #[arg(long = "type", requires = "wasm")]
+        ty: Option<String>,
+    },
+    /// Build an unsigned transaction invoking a contract, as base64 XDR ready for simulation
+    /// and signing.
+    BuildInvocation {
+        /// Source account (G...).
+        #[arg(long)]
+        source: String,
+        /// The source account's current sequence number plus one.
+        #[arg(long)]
+        sequence: i64,
+        #[arg(long)]
+        contract: String,
+        #[arg(long = "fn")]
+        function: String,
+        /// Argument, in the syntax of `encode-sc-val`.
+        #[arg(long = "arg")]
+        args: Vec<String>,
+        #[arg(long, default_value_t = 100)]
+        fee: u32,
+        /// Contract wasm, to parse the arguments after the function's spec.
+        #[arg(long)]
+        wasm: Option<PathBuf>,
+        /// Reuse the footprint of a previous invocation, given its transaction hash.
+        #[arg(long)]
+        footprint_from: Option<String>,
+    },
+    /// Convert a strkey to hex, or a hex contract id to its strkey.
+    Strkey { value: String },
+    /// Derive a contract id ahead of deployment.
+    ContractId {
+        /// Deployer account (G...), for contracts created by a source account.
+        #[arg(long)]
+        source: Option<String>,
+        /// Hex-encoded ed25519 public key, for contracts created with a signature.
+        #[arg(long)]
+        ed25519: Option<String>,
+        /// Deploying contract id, for contracts created by a contract.
+        #[arg(long)]
+        contract: Option<String>,
+        /// Wrapped Stellar asset, `native` or `CODE:ISSUER`.
+        #[arg(long)]
+        asset: Option<String>,
+        /// Hex-encoded 32 bytes salt.
+        #[arg(long)]
+        salt: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ContractCommand {
+    /// List the latest invocations of the contract, or the ones in a ledger or time range
+    /// (oldest first).
+    Invocations {
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        #[arg(long, default_value_t = 10)]
+        max_pages: usize,
+        /// First ledger of the range.
+        #[arg(long)]
+        from_ledger: Option<u32>,
+        /// Ledger the range ends before.
+        #[arg(long)]
+        to_ledger: Option<u32>,
+        /// Start of the range, RFC 3339.
+        #[arg(long)]
+        from: Option<DateTime<Utc>>,
+        /// End of the range (exclusive), RFC 3339.
+        #[arg(long)]
+        to: Option<DateTime<Utc>>,
+    },
+    /// Check that the deployed code matches a locally built wasm, exits with 1 when it
+    /// doesn't.
+    Verify {
+        wasm: PathBuf,
+        /// Transaction that deployed the contract, searched in the latest operations when
+        /// not set.
+        #[arg(long)]
+        deployment: Option<String>,
+        #[arg(long, default_value_t = 10)]
+        max_pages: usize,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum XdrType {
+    TransactionEnvelope,
+    TransactionResult,
+    TransactionMeta,
+    ScVal,
+    LedgerFootprint,
+    ContractEvent,
+}
+
+#[derive(Serialize)]
+struct EventRecord {
+    tx: String,
+    at: String,
+    event: ContractEvent,
+}
+
+enum Scope<'a> {
+    All,
+    Contract(&'a str),
+    // `url` has to be a page of the account's transactions.
+    Account(&'a str),
+}
+
+async fn scan(
+    horizon: &str,
+    network_passphrase: &str,
+    mut url: String,
+    scope: Scope<'_>,
+    limit: usize,
+    max_pages: usize,
+) -> Vec<Processed> {
+    let mut out = vec![];
+
+    for _ in 0..max_pages {
+        let page = match scope {
+            Scope::All => get_operations(horizon, Some(network_passphrase), &url).await,
+            Scope::Contract(id) => {
+                get_contract_operations(horizon, Some(network_passphrase), &url, id).await
+            }
+            Scope::Account(id) => {
+                get_account_transactions(Some(network_passphrase), &url, id).await
+            }
+        };
+        let (mut processed, first, next) = page.unwrap_or_else(|e| fail(&e.to_string()));
+        out.append(&mut processed);
+
+        if out.len() >= limit || first.is_none() {
+            break;
+        }
+        url = next;
+    }
+
+    out.truncate(limit);
+    out
+}
+
+fn decode_xdr(ty: XdrType, xdr: &str) -> Result<serde_json::Value, stellar_xdr::Error> {
+    let value = match ty {
+        XdrType::TransactionEnvelope => {
+            serde_json::to_value(TransactionEnvelope::from_xdr_base64(xdr)?)
+        }
+        XdrType::TransactionResult => {
+            serde_json::to_value(TransactionResult::from_xdr_base64(xdr)?)
+        }
+        XdrType::TransactionMeta => serde_json::to_value(TransactionMeta::from_xdr_base64(xdr)?),
+        XdrType::ScVal => serde_json::to_value(ScVal::from_xdr_base64(xdr)?),
+        XdrType::LedgerFootprint => serde_json::to_value(LedgerFootprint::from_xdr_base64(xdr)?),
+        XdrType::ContractEvent => serde_json::to_value(ContractEvent::from_xdr_base64(xdr)?),
+    };
+
+    Ok(value.unwrap())
+}
+
+fn derive_contract_id(
+    passphrase: &str,
+    source: Option<String>,
+    ed25519: Option<String>,
+    contract: Option<String>,
+    asset: Option<String>,
+    salt: Option<String>,
+) -> Result<String, &'static str> {
+    let salt = || {
+        salt.as_deref()
+            .and_then(contract_id::parse_bytes32)
+            .ok_or("--salt must be 32 hex-encoded bytes")
+    };
+
+    let derived = match (source, ed25519, contract, asset) {
+        (Some(source), None, None, None) => {
+            let account = strkey::to_account_id(&source).ok_or("--source must be a G... strkey")?;
+            contract_id::from_source_account(passphrase, &account, salt()?)
+        }
+        (None, Some(ed25519), None, None) => {
+            let key = contract_id::parse_bytes32(&ed25519)
+                .ok_or("--ed25519 must be 32 hex-encoded bytes")?;
+            contract_id::from_ed25519(passphrase, key, salt()?)
+        }
+        (None, None, Some(contract), None) => {
+            contract_id::from_contract(passphrase, &contract, salt()?)
+        }
+        (None, None, None, Some(asset)) => {
+            let asset = contract_id::parse_asset(&asset)
+                .ok_or("--asset must be `native` or `CODE:ISSUER`")?;
+            contract_id::from_asset(passphrase, &asset)
+        }
+        _ => return Err("expected exactly one of --source, --ed25519, --contract or --asset"),
+    };
+
+    derived.ok_or("could not derive the contract id")
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {message}");
+    exit(1)
+}
+
+fn read_spec(path: &Path) -> Vec<ScSpecEntry> {
+    let wasm =
+        fs::read(path).unwrap_or_else(|e| fail(&format!("couldn't read {}: {e}", path.display())));
+    spec::from_wasm(&wasm).unwrap_or_else(|| fail("invalid wasm module"))
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let horizon = cli.horizon.trim_end_matches('/');
+    let network_passphrase = cli.network_passphrase.as_str();
+
+    match cli.command {
+        Command::Tx { hash, verify, wasm } => match get_transaction(horizon, &hash).await {
+            Some(tx) => {
+                let spec = wasm.map(|path| read_spec(&path));
+                let report = if verify {
+                    check_signatures(horizon, &tx, network_passphrase).await
+                } else {
+                    None
+                };
+                let processed = process_tx_operations(tx, Some(network_passphrase));
+                if processed.is_empty() {
+                    fail("the transaction couldn't be decoded");
+                }
+                let errors = spec.map(|spec| {
+                    processed
+                        .iter()
+                        .filter_map(|processed| match &processed.body {
+                            Event::Invocation(invocation) => Some(invocation),
+                            _ => None,
+                        })
+                        .flat_map(|invocation| spec::invocation_errors(&spec, invocation))
+                        .collect::<Vec<_>>()
+                });
+                print_tx(cli.output, &processed, errors.as_deref(), report.as_ref());
+            }
+            None => fail("transaction not found"),
+        },
+        Command::TestCase { hash } => match get_transaction(horizon, &hash).await {
+            Some(tx) => {
+                // host function invocations are the only operation of their transaction.
+                let invocation = process_tx_operations(tx, Some(network_passphrase))
+                    .into_iter()
+                    .find_map(|processed| match processed.body {
+                        Event::Invocation(invocation) => Some(invocation),
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| fail("the transaction doesn't invoke a contract"));
+                print!("{}", testgen::test_skeleton(&hash, &invocation));
+            }
+            None => fail("transaction not found"),
+        },
+        Command::Contract {
+            id,
+            command:
+                ContractCommand::Invocations {
+                    limit,
+                    max_pages,
+                    from_ledger,
+                    to_ledger,
+                    from,
+                    to,
+                },
+        } => {
+            let range = Range {
+                from_ledger,
+                to_ledger,
+                from,
+                to,
+            };
+            let processed = if range.is_bounded() {
+                let mut processed =
+                    get_operations_in_range(horizon, Some(network_passphrase), Some(&id), range)
+                        .await
+                        .unwrap_or_else(|e| fail(&e.to_string()));
+                processed.truncate(limit);
+                processed
+            } else {
+                let url = format!("{horizon}/operations?order=desc&limit=200");
+                scan(
+                    horizon,
+                    network_passphrase,
+                    url,
+                    Scope::Contract(&id),
+                    limit,
+                    max_pages,
+                )
+                .await
+            };
+            print_records(
+                cli.output,
+                &processed,
+                PROCESSED_HEADER,
+                output::processed_row,
+            );
+        }
+        Command::Contract {
+            id,
+            command:
+                ContractCommand::Verify {
+                    wasm,
+                    deployment,
+                    max_pages,
+                },
+        } => {
+            let local = fs::read(&wasm)
+                .unwrap_or_else(|e| fail(&format!("couldn't read {}: {e}", wasm.display())));
+            let id = strkey::normalize_contract_id(&id).unwrap_or(id);
+            let processed = match deployment {
+                Some(hash) => match get_transaction(horizon, &hash).await {
+                    Some(tx) => process_tx_operations(tx, Some(network_passphrase)),
+                    None => fail("deployment transaction not found"),
+                },
+                None => {
+                    let url = format!("{horizon}/operations?order=desc&limit=200");
+                    scan(
+                        horizon,
+                        network_passphrase,
+                        url,
+                        Scope::All,
+                        usize::MAX,
+                        max_pages,
+                    )
+                    .await
+                }
+            };
+            let deployed = processed
+                .into_iter()
+                .find_map(|p| match p.body {
+                    Event::Deployment(deployed) if deployed.id == id => Some(deployed),
+                    _ => None,
+                })
+                .unwrap_or_else(|| fail("deployment of the contract not found"));
+
+            let verification =
+                verify::verify(&local, &deployed).unwrap_or_else(|| fail("invalid wasm module"));
+            print_verification(cli.output, &verification);
+            if !verification.matches {
+                exit(1);
+            }
+        }
+        Command::Account {
+            id,
+            limit,
+            max_pages,
+        } => {
+            let url = format!("{horizon}/accounts/{id}/transactions?order=desc&limit=200");
+            let processed = scan(
+                horizon,
+                network_passphrase,
+                url,
+                Scope::Account(&id),
+                limit,
+                max_pages,
+            )
+            .await;
+            print_records(
+                cli.output,
+                &processed,
+                PROCESSED_HEADER,
+                output::processed_row,
+            );
+        }
+        Command::Events {
+            contract,
+            limit,
+            max_pages,
+        } => {
+            let url = format!("{horizon}/operations?order=desc&limit=200");
+            let contract = contract.map(|id| strkey::normalize_contract_id(&id).unwrap_or(id));
+            let mut records = vec![];
+            for processed in scan(
+                horizon,
+                network_passphrase,
+                url,
+                Scope::All,
+                usize::MAX,
+                max_pages,
+            )
+            .await
+            {
+                if let Event::Invocation(invocation) = processed.body {
+                    for event in invocation.events.into_iter().flatten() {
+                        let emitted_by =
+                            event.contract_id.as_ref().map(|id| strkey::contract(id.0));
+                        if contract.is_none() || emitted_by == contract {
+                            records.push(EventRecord {
+                                tx: processed.tx.clone(),
+                                at: processed.at.to_string(),
+                                event,
+                            });
+                        }
+                    }
+                }
+            }
+            records.truncate(limit);
+
+            print_records(
+                cli.output,
+                &records,
+                &["TX", "AT", "CONTRACT", "TOPICS", "DATA"],
+                |record| {
+                    let ContractEventBody::V0(body) = &record.event.body;
+                    vec![
+                        record.tx.clone(),
+                        record.at.clone(),
+                        record
+                            .event
+                            .contract_id
+                            .as_ref()
+                            .map(|id| strkey::contract(id.0))
+                            .unwrap_or_default(),
+                        serde_json::to_string(&body.topics).unwrap(),
+                        serde_json::to_string(&body.data).unwrap(),
+                    ]
+                },
+            );
+        }
+        Command::DecodeXdr { ty, xdr } => match decode_xdr(ty, &xdr) {
+            Ok(value) => print_value(cli.output, &value),
+            Err(_) => fail("invalid XDR for the given type"),
+        },
+        Command::EncodeScVal { value, wasm, ty } => {
+            let entries = wasm.map(|path| read_spec(&path)).unwrap_or_default();
+            let ty = ty.map(|name| {
+                ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                    name: name
+                        .as_bytes()
+                        .to_vec()
+                        .try_into()
+                        .unwrap_or_else(|_| fail("invalid type name")),
+                })
+            });
+            match scval::parse(&value, ty.as_ref(), &entries) {
+                Ok(val) => println!("{}", val.to_xdr_base64().unwrap()),
+                Err(e) => {
+                    eprintln!("{}", e.pointer(&value));
+                    fail(&e.to_string())
+                }
+            }
+        }
+        Command::BuildInvocation {
+            source,
+            sequence,
+            contract,
+            function,
+            args,
+            fee,
+            wasm,
+            footprint_from,
+        } => {
+            let entries = wasm.map(|path| read_spec(&path)).unwrap_or_default();
+            let args = parse_args(&entries, &function, &args).unwrap_or_else(|(i, e)| {
+                if let Some(arg) = args.get(i) {
+                    eprintln!("{}", e.pointer(arg));
+                }
+                fail(&format!("argument {i}: {e}"))
+            });
+            let footprint = match footprint_from {
+                Some(hash) => match get_transaction(horizon, &hash).await {
+                    Some(tx) => process_tx_operations(tx, Some(network_passphrase))
+                        .into_iter()
+                        .find_map(|processed| match processed.body {
+                            Event::Invocation(invocation) => invocation.footprint,
+                            _ => None,
+                        }),
+                    None => fail("transaction not found"),
+                },
+                None => None,
+            };
+
+            let tx = InvocationTx {
+                source_account: source,
+                sequence,
+                contract_id: contract,
+                function,
+                args,
+                fee,
+                footprint,
+            };
+            match tx.to_xdr_base64() {
+                Ok(xdr) => println!("{xdr}"),
+                Err(e) => fail(e),
+            }
+        }
+        Command::ContractId {
+            source,
+            ed25519,
+            contract,
+            asset,
+            salt,
+        } => match derive_contract_id(network_passphrase, source, ed25519, contract, asset, salt) {
+            Ok(id) => match cli.output {
+                Format::Table => println!("{id}"),
+                _ => print_value(cli.output, &id),
+            },
+            Err(message) => fail(message),
+        },
+        Command::Strkey { value } => {
+            let converted = match strkey::kind(&value) {
+                Some(_) => strkey::to_hex(&value),
+                None => strkey::from_hex(strkey::Kind::Contract, &value),
+            };
+            match (converted, cli.output) {
+                (Some(converted), Format::Table) => println!("{converted}"),
+                (Some(converted), _) => print_value(cli.output, &converted),
+                (None, _) => fail("expected a strkey or 32 hex-encoded bytes"),
+            }
+        }
+    }
+}
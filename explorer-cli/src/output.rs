@@ -0,0 +1,348 @@
+use clap::ValueEnum;
+use explorer_common::{
+    signatures::SignatureReport,
+    spec::{ContractError, UNKNOWN_FAILURE_CODE},
+    types::common::{Event, Processed},
+    verify::Verification,
+    wasm,
+};
+use serde::Serialize;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Ndjson,
+}
+
+pub fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths = header.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    };
+
+    line(header.to_vec());
+    for row in rows {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
+
+pub fn print_records<T: Serialize>(
+    format: Format,
+    records: &[T],
+    header: &[&str],
+    row: impl Fn(&T) -> Vec<String>,
+) {
+    match format {
+        Format::Table => print_table(header, &records.iter().map(row).collect::<Vec<_>>()),
+        Format::Json => println!("{}", serde_json::to_string_pretty(records).unwrap()),
+        Format::Ndjson => {
+            for record in records {
+                println!("{}", serde_json::to_string(record).unwrap());
+            }
+        }
+    }
+}
+
+pub fn print_value<T: Serialize>(format: Format, value: &T) {
+    match format {
+        Format::Table | Format::Json => {
+            println!("{}", serde_json::to_string_pretty(value).unwrap())
+        }
+        Format::Ndjson => println!("{}", serde_json::to_string(value).unwrap()),
+    }
+}
+
+pub const PROCESSED_HEADER: &[&str] =
+    &["TX", "LEDGER", "AT", "SOURCE", "KIND", "CONTRACT", "ACTION"];
+
+pub fn processed_row(processed: &Processed) -> Vec<String> {
+    let (kind, contract, action) = match &processed.body {
+        Event::Invocation(invocation) => {
+            ("invoke", invocation.id.clone(), invocation.function.clone())
+        }
+        Event::Deployment(deployed) => ("deploy", deployed.id.clone(), String::new()),
+        event => ("classic", String::new(), event.action()),
+    };
+
+    vec![
+        processed.tx.clone(),
+        processed.ledger.to_string(),
+        processed.at.to_string(),
+        processed.source_account.clone(),
+        kind.to_string(),
+        contract,
+        action,
+    ]
+}
+
+fn print_processed(format: Format, processed: &Processed) {
+    if !matches!(format, Format::Table) {
+        return print_value(format, processed);
+    }
+
+    let mut rows = vec![
+        vec!["tx".to_string(), processed.tx.clone()],
+        vec!["ledger".to_string(), processed.ledger.to_string()],
+        vec!["at".to_string(), processed.at.to_string()],
+        vec!["source".to_string(), processed.source_account.clone()],
+    ];
+
+    if let Some(fee_bump) = &processed.fee_bump {
+        rows.push(vec!["fee source".to_string(), fee_bump.fee_source.clone()]);
+        rows.push(vec!["fee bump fee".to_string(), fee_bump.fee.to_string()]);
+    }
+    if let Some(memo) = &processed.memo {
+        rows.push(vec![
+            "memo".to_string(),
+            serde_json::to_string(memo).unwrap(),
+        ]);
+    }
+    if let Some(preconditions) = &processed.preconditions {
+        rows.push(vec![
+            "preconditions".to_string(),
+            serde_json::to_string(preconditions).unwrap(),
+        ]);
+    }
+
+    match &processed.body {
+        Event::Invocation(invocation) => {
+            rows.push(vec!["contract".to_string(), invocation.id.clone()]);
+            rows.push(vec!["function".to_string(), invocation.function.clone()]);
+            for (i, arg) in invocation.args.iter().enumerate() {
+                rows.push(vec![
+                    format!("arg {i}"),
+                    serde_json::to_string(arg).unwrap(),
+                ]);
+            }
+            rows.push(vec![
+                "result".to_string(),
+                serde_json::to_string(&invocation.result).unwrap(),
+            ]);
+            if let Some(error) = &invocation.error {
+                rows.push(vec!["error".to_string(), error.clone()]);
+                rows.push(vec![
+                    "error code".to_string(),
+                    UNKNOWN_FAILURE_CODE.to_string(),
+                ]);
+            }
+            for (i, event) in invocation.events.iter().flatten().enumerate() {
+                rows.push(vec![
+                    format!("event {i}"),
+                    serde_json::to_string(event).unwrap(),
+                ]);
+            }
+        }
+        Event::Deployment(deployed) => {
+            rows.push(vec!["contract".to_string(), deployed.id.clone()]);
+            if let Some(derived_id) = &deployed.derived_id {
+                let check = if derived_id == &deployed.id {
+                    "matches"
+                } else {
+                    "MISMATCH"
+                };
+                rows.push(vec![
+                    "derived id".to_string(),
+                    format!("{derived_id} ({check})"),
+                ]);
+            }
+            rows.push(vec![
+                "wasm size".to_string(),
+                deployed.bytes.len().to_string(),
+            ]);
+            match wasm::inspect(&deployed.bytes) {
+                Some(info) => {
+                    rows.push(vec!["wasm hash".to_string(), info.hash]);
+                    if let Some(version) = info.interface_version {
+                        rows.push(vec![
+                            "env interface version".to_string(),
+                            version.to_string(),
+                        ]);
+                    }
+                    rows.push(vec!["exports".to_string(), info.exports.join(", ")]);
+                    rows.push(vec![
+                        "host imports".to_string(),
+                        info.imports
+                            .iter()
+                            .map(|import| format!("{}.{}", import.module, import.name))
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    ]);
+                    for section in info.custom_sections {
+                        rows.push(vec![
+                            format!("section {}", section.name),
+                            format!("{} bytes, {}", section.size, section.hash),
+                        ]);
+                    }
+                }
+                None => rows.push(vec!["wasm".to_string(), "invalid module".to_string()]),
+            }
+        }
+        event => {
+            rows.push(vec!["operation".to_string(), event.action()]);
+            rows.push(vec![
+                "details".to_string(),
+                serde_json::to_string(event).unwrap(),
+            ]);
+        }
+    }
+
+    print_table(&["FIELD", "VALUE"], &rows);
+}
+
+/// The parts of a transaction's report, kept in one document outside of the table format.
+#[derive(Serialize)]
+struct TxReport<'a> {
+    operations: &'a [Processed],
+    errors: Option<&'a [ContractError]>,
+    signatures: Option<&'a SignatureReport>,
+}
+
+pub fn print_tx(
+    format: Format,
+    processed: &[Processed],
+    errors: Option<&[ContractError]>,
+    signatures: Option<&SignatureReport>,
+) {
+    if !matches!(format, Format::Table) {
+        return print_value(
+            format,
+            &TxReport {
+                operations: processed,
+                errors,
+                signatures,
+            },
+        );
+    }
+
+    for (i, processed) in processed.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_processed(format, processed);
+    }
+    if let Some(errors) = errors {
+        print_contract_errors(format, errors);
+    }
+    if let Some(report) = signatures {
+        print_signatures(format, report);
+    }
+}
+
+fn print_contract_errors(format: Format, errors: &[ContractError]) {
+    if !matches!(format, Format::Table) {
+        return print_value(format, errors);
+    }
+
+    println!();
+    print_table(
+        &["CODE", "ERROR"],
+        &errors
+            .iter()
+            .map(|error| {
+                vec![
+                    error.code.to_string(),
+                    format!("{}::{}", error.enum_name, error.name),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    );
+}
+
+pub fn print_verification(format: Format, verification: &Verification) {
+    if !matches!(format, Format::Table) {
+        return print_value(format, verification);
+    }
+
+    print_table(
+        &["CONTRACT", "LOCAL HASH", "DEPLOYED HASH", "MATCHES"],
+        &[vec![
+            verification.contract_id.clone(),
+            verification.local_hash.clone(),
+            verification.deployed_hash.clone(),
+            if verification.matches { "yes" } else { "no" }.to_string(),
+        ]],
+    );
+    if verification.matches {
+        return;
+    }
+
+    let missing = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    println!();
+    print_table(
+        &["ITEM", "LOCAL", "DEPLOYED"],
+        &verification
+            .differences
+            .iter()
+            .map(|difference| {
+                vec![
+                    difference.item.clone(),
+                    missing(&difference.local),
+                    missing(&difference.deployed),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    );
+}
+
+fn print_signatures(format: Format, report: &SignatureReport) {
+    if !matches!(format, Format::Table) {
+        return print_value(format, report);
+    }
+
+    let yes_no = |b: bool| if b { "yes" } else { "no" }.to_string();
+
+    println!();
+    print_table(
+        &["HASH", "MATCHES HORIZON"],
+        &[vec![report.hash.clone(), yes_no(report.hash_matches)]],
+    );
+
+    println!();
+    print_table(
+        &["HINT", "SIGNER"],
+        &report
+            .signatures
+            .iter()
+            .map(|check| {
+                vec![
+                    check.hint.clone(),
+                    check
+                        .signer
+                        .clone()
+                        .unwrap_or_else(|| "unknown".to_string()),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    println!();
+    print_table(
+        &["ACCOUNT", "SIGNED WEIGHT", "LOW", "MEDIUM", "HIGH"],
+        &report
+            .accounts
+            .iter()
+            .map(|account| {
+                vec![
+                    account.account.clone(),
+                    account.signed_weight.to_string(),
+                    yes_no(account.low),
+                    yes_no(account.medium),
+                    yes_no(account.high),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    );
+}
@@ -0,0 +1,20 @@
+use super::types;
+
+pub async fn get_account(base_url: &str, id: &str) -> Option<types::account::Response> {
+    let url = format!("{base_url}/accounts/{id}");
+
+    let result = reqwest::get(&url).await;
+    match result {
+        Ok(resp) => {
+            if resp.status().is_success() {
+                match resp.json::<types::account::Response>().await {
+                    Ok(resp) => Some(resp),
+                    Err(_) => None,
+                }
+            } else {
+                None
+            }
+        }
+        Err(_) => None,
+    }
+}
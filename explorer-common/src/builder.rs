@@ -0,0 +1,216 @@
+use serde_derive::{Deserialize, Serialize};
+use stellar_xdr::{
+    HostFunction, InvokeHostFunctionOp, LedgerFootprint, Memo, MuxedAccount, Operation,
+    OperationBody, Preconditions, PublicKey, ScSpecEntry, ScSpecFunctionV0, ScVal, ScVec,
+    SequenceNumber, Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope,
+    WriteXdr,
+};
+
+use crate::{
+    scval::{self, ParseError},
+    strkey,
+    types::common::Invocation,
+};
+
+/// An unsigned transaction invoking a contract.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InvocationTx {
+    pub source_account: String,
+    // the source account's current sequence number plus one.
+    pub sequence: i64,
+    pub contract_id: String,
+    pub function: String,
+    pub args: Vec<ScVal>,
+    pub fee: u32,
+    // left empty when not set, for simulation to fill in.
+    pub footprint: Option<LedgerFootprint>,
+}
+
+impl InvocationTx {
+    /// Same call and footprint as a previous invocation, `None` when some of its arguments
+    /// couldn't be decoded.
+    pub fn from_invocation(
+        invocation: &Invocation,
+        source_account: &str,
+        sequence: i64,
+        fee: u32,
+    ) -> Option<Self> {
+        Some(Self {
+            source_account: source_account.to_string(),
+            sequence,
+            contract_id: invocation.id.clone(),
+            function: invocation.function.clone(),
+            args: invocation.args.iter().cloned().collect::<Option<_>>()?,
+            fee,
+            footprint: invocation.footprint.clone(),
+        })
+    }
+
+    pub fn envelope(&self) -> Result<TransactionEnvelope, &'static str> {
+        let PublicKey::PublicKeyTypeEd25519(key) = strkey::to_account_id(&self.source_account)
+            .ok_or("the source account must be a G... strkey")?
+            .0;
+        let contract_id = strkey::normalize_contract_id(&self.contract_id)
+            .and_then(|id| strkey::to_sc_val(&id))
+            .ok_or("invalid contract id")?;
+        let function = scval::parse(&format!("sym:{}", self.function), None, &[])
+            .map_err(|_| "the function name isn't a valid symbol")?;
+
+        let mut parameters = vec![contract_id, function];
+        parameters.extend(self.args.iter().cloned());
+        let footprint = self.footprint.clone().unwrap_or(LedgerFootprint {
+            read_only: vec![].try_into().unwrap(),
+            read_write: vec![].try_into().unwrap(),
+        });
+
+        let tx = Transaction {
+            source_account: MuxedAccount::Ed25519(key),
+            fee: self.fee,
+            seq_num: SequenceNumber(self.sequence),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                    function: HostFunction::InvokeContract,
+                    parameters: ScVec(parameters.try_into().map_err(|_| "too many arguments")?),
+                    footprint,
+                }),
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        };
+
+        Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: vec![].try_into().unwrap(),
+        }))
+    }
+
+    /// Base64 XDR of the unsigned envelope, ready for simulation and signing.
+    pub fn to_xdr_base64(&self) -> Result<String, &'static str> {
+        self.envelope()?
+            .to_xdr_base64()
+            .map_err(|_| "the transaction couldn't be encoded")
+    }
+}
+
+/// Parses the arguments of `function`, guided by its inputs in `entries` when the contract
+/// spec is known. Errors come with the index of the argument.
+pub fn parse_args(
+    entries: &[ScSpecEntry],
+    function: &str,
+    args: &[String],
+) -> Result<Vec<ScVal>, (usize, ParseError)> {
+    let inputs = entries.iter().find_map(|entry| match entry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 { name, inputs, .. })
+            if name.to_string_lossy() == function =>
+        {
+            Some(inputs)
+        }
+        _ => None,
+    });
+    if let Some(inputs) = inputs {
+        if inputs.len() != args.len() {
+            return Err((
+                args.len().min(inputs.len()),
+                ParseError {
+                    position: 0,
+                    message: format!(
+                        "{function} takes {} arguments, found {}",
+                        inputs.len(),
+                        args.len()
+                    ),
+                },
+            ));
+        }
+    }
+
+    args.iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            let ty = inputs
+                .and_then(|inputs| inputs.get(i))
+                .map(|input| &input.type_);
+            scval::parse(arg, ty, entries).map_err(|e| (i, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use stellar_xdr::{ReadXdr, ScObject, Uint256};
+
+    use super::*;
+
+    const ACCOUNT: &str = "GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZX";
+    const CONTRACT: &str = "CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O";
+
+    fn invocation_tx(args: Vec<ScVal>) -> InvocationTx {
+        InvocationTx {
+            source_account: String::from(ACCOUNT),
+            sequence: 42,
+            contract_id: String::from(CONTRACT),
+            function: String::from("transfer"),
+            args,
+            fee: 1000,
+            footprint: None,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let args = parse_args(
+            &[],
+            "transfer",
+            &[String::from(ACCOUNT), String::from("i128:1000")],
+        )
+        .unwrap();
+        let xdr = invocation_tx(args.clone()).to_xdr_base64().unwrap();
+
+        let tx = match TransactionEnvelope::from_xdr_base64(&xdr).unwrap() {
+            TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) => {
+                assert!(signatures.is_empty());
+                tx
+            }
+            _ => panic!("expected a v1 envelope"),
+        };
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        assert_eq!(tx.source_account, MuxedAccount::Ed25519(Uint256(key)));
+        assert_eq!(tx.seq_num, SequenceNumber(42));
+        assert_eq!(tx.fee, 1000);
+        assert_eq!(tx.operations.len(), 1);
+
+        let op = match &tx.operations[0].body {
+            OperationBody::InvokeHostFunction(op) => op,
+            _ => panic!("expected a host function invocation"),
+        };
+        assert_eq!(op.function, HostFunction::InvokeContract);
+        let mut expected = vec![
+            ScVal::Object(Some(ScObject::Bytes(key.to_vec().try_into().unwrap()))),
+            ScVal::Symbol("transfer".as_bytes().to_vec().try_into().unwrap()),
+        ];
+        expected.extend(args);
+        assert_eq!(op.parameters.to_vec(), expected);
+        assert!(op.footprint.read_only.is_empty());
+        assert!(op.footprint.read_write.is_empty());
+    }
+
+    #[test]
+    fn invalid_transaction() {
+        let mut tx = invocation_tx(vec![]);
+        tx.source_account = String::from(CONTRACT);
+        assert_eq!(
+            tx.envelope().unwrap_err(),
+            "the source account must be a G... strkey"
+        );
+
+        let mut tx = invocation_tx(vec![]);
+        tx.function = String::from("not a symbol");
+        assert_eq!(
+            tx.envelope().unwrap_err(),
+            "the function name isn't a valid symbol"
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use stellar_xdr::{
+    AlphaNum12, AlphaNum4, Asset, ChangeTrustAsset, ChangeTrustOp, CreateAccountOp, OperationBody,
+    PathPaymentStrictReceiveOp, PathPaymentStrictSendOp, PaymentOp, SetTrustLineFlagsOp,
+};
+
+use crate::{
+    strkey,
+    types::common::{
+        AccountMerge, ChangeTrust, CreateAccount, Event, PathPayment, Payment, TrustlineFlags,
+    },
+};
+
+const STROOPS_PER_UNIT: i64 = 10_000_000;
+
+/// Formats an amount in stroops as a decimal string with 7 digits, like Horizon does.
+pub fn amount(stroops: i64) -> String {
+    let sign = if stroops < 0 { "-" } else { "" };
+    let stroops = stroops.unsigned_abs();
+    let unit = STROOPS_PER_UNIT as u64;
+    format!("{sign}{}.{:07}", stroops / unit, stroops % unit)
+}
+
+fn code(code: &[u8]) -> String {
+    String::from_utf8_lossy(code)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+/// `native` or `CODE:ISSUER`.
+pub fn asset(asset: &Asset) -> String {
+    match asset {
+        Asset::Native => String::from("native"),
+        Asset::CreditAlphanum4(AlphaNum4 { asset_code, issuer }) => {
+            format!("{}:{}", code(&asset_code.0), strkey::account_id(issuer))
+        }
+        Asset::CreditAlphanum12(AlphaNum12 { asset_code, issuer }) => {
+            format!("{}:{}", code(&asset_code.0), strkey::account_id(issuer))
+        }
+    }
+}
+
+fn change_trust_asset(line: &ChangeTrustAsset) -> String {
+    match line {
+        ChangeTrustAsset::Native => String::from("native"),
+        ChangeTrustAsset::CreditAlphanum4(AlphaNum4 { asset_code, issuer }) => {
+            format!("{}:{}", code(&asset_code.0), strkey::account_id(issuer))
+        }
+        ChangeTrustAsset::CreditAlphanum12(AlphaNum12 { asset_code, issuer }) => {
+            format!("{}:{}", code(&asset_code.0), strkey::account_id(issuer))
+        }
+        ChangeTrustAsset::PoolShare(_) => String::from("liquidity pool share"),
+    }
+}
+
+fn operation_name(body: &OperationBody) -> &'static str {
+    match body {
+        OperationBody::CreateAccount(_) => "CreateAccount",
+        OperationBody::Payment(_) => "Payment",
+        OperationBody::PathPaymentStrictReceive(_) => "PathPaymentStrictReceive",
+        OperationBody::ManageSellOffer(_) => "ManageSellOffer",
+        OperationBody::CreatePassiveSellOffer(_) => "CreatePassiveSellOffer",
+        OperationBody::SetOptions(_) => "SetOptions",
+        OperationBody::ChangeTrust(_) => "ChangeTrust",
+        OperationBody::AllowTrust(_) => "AllowTrust",
+        OperationBody::AccountMerge(_) => "AccountMerge",
+        OperationBody::Inflation => "Inflation",
+        OperationBody::ManageData(_) => "ManageData",
+        OperationBody::BumpSequence(_) => "BumpSequence",
+        OperationBody::ManageBuyOffer(_) => "ManageBuyOffer",
+        OperationBody::PathPaymentStrictSend(_) => "PathPaymentStrictSend",
+        OperationBody::CreateClaimableBalance(_) => "CreateClaimableBalance",
+        OperationBody::ClaimClaimableBalance(_) => "ClaimClaimableBalance",
+        OperationBody::BeginSponsoringFutureReserves(_) => "BeginSponsoringFutureReserves",
+        OperationBody::EndSponsoringFutureReserves => "EndSponsoringFutureReserves",
+        OperationBody::RevokeSponsorship(_) => "RevokeSponsorship",
+        OperationBody::Clawback(_) => "Clawback",
+        OperationBody::ClawbackClaimableBalance(_) => "ClawbackClaimableBalance",
+        OperationBody::SetTrustLineFlags(_) => "SetTrustLineFlags",
+        OperationBody::LiquidityPoolDeposit(_) => "LiquidityPoolDeposit",
+        OperationBody::LiquidityPoolWithdraw(_) => "LiquidityPoolWithdraw",
+        OperationBody::InvokeHostFunction(_) => "InvokeHostFunction",
+    }
+}
+
+pub fn process_classic_op(body: &OperationBody) -> Event {
+    match body {
+        OperationBody::CreateAccount(CreateAccountOp {
+            destination,
+            starting_balance,
+        }) => Event::CreateAccount(CreateAccount {
+            destination: strkey::account_id(destination),
+            starting_balance: amount(*starting_balance),
+        }),
+        OperationBody::Payment(PaymentOp {
+            destination,
+            asset: payment_asset,
+            amount: payment_amount,
+        }) => Event::Payment(Payment {
+            destination: strkey::muxed_account(destination),
+            asset: asset(payment_asset),
+            amount: amount(*payment_amount),
+        }),
+        OperationBody::PathPaymentStrictReceive(PathPaymentStrictReceiveOp {
+            send_asset,
+            send_max,
+            destination,
+            dest_asset,
+            dest_amount,
+            ..
+        }) => Event::PathPayment(PathPayment {
+            destination: strkey::muxed_account(destination),
+            send_asset: asset(send_asset),
+            send_amount: amount(*send_max),
+            dest_asset: asset(dest_asset),
+            dest_amount: amount(*dest_amount),
+            strict_send: false,
+        }),
+        OperationBody::PathPaymentStrictSend(PathPaymentStrictSendOp {
+            send_asset,
+            send_amount,
+            destination,
+            dest_asset,
+            dest_min,
+            ..
+        }) => Event::PathPayment(PathPayment {
+            destination: strkey::muxed_account(destination),
+            send_asset: asset(send_asset),
+            send_amount: amount(*send_amount),
+            dest_asset: asset(dest_asset),
+            dest_amount: amount(*dest_min),
+            strict_send: true,
+        }),
+        OperationBody::ChangeTrust(ChangeTrustOp { line, limit }) => {
+            Event::ChangeTrust(ChangeTrust {
+                asset: change_trust_asset(line),
+                limit: amount(*limit),
+            })
+        }
+        OperationBody::SetTrustLineFlags(SetTrustLineFlagsOp {
+            trustor,
+            asset: trustline_asset,
+            clear_flags,
+            set_flags,
+        }) => Event::TrustlineFlags(TrustlineFlags {
+            trustor: strkey::account_id(trustor),
+            asset: asset(trustline_asset),
+            set_flags: *set_flags,
+            clear_flags: *clear_flags,
+        }),
+        OperationBody::AccountMerge(destination) => Event::AccountMerge(AccountMerge {
+            destination: strkey::muxed_account(destination),
+        }),
+        _ => Event::Other(operation_name(body).to_string()),
+    }
+}
@@ -0,0 +1,154 @@
+use sha2::{Digest, Sha256};
+use stellar_xdr::{
+    AccountId, AlphaNum12, AlphaNum4, Asset, AssetCode12, AssetCode4, Hash, HashIdPreimage,
+    HashIdPreimageContractId, HashIdPreimageEd25519ContractId, HashIdPreimageFromAsset,
+    HashIdPreimageSourceAccountContractId, Uint256, WriteXdr,
+};
+
+use crate::strkey;
+
+pub const FUTURENET_PASSPHRASE: &str = "Test SDF Future Network ; October 2022";
+
+pub fn network_id(passphrase: &str) -> Hash {
+    Hash(Sha256::digest(passphrase.as_bytes()).into())
+}
+
+fn derive(preimage: HashIdPreimage) -> Option<String> {
+    let preimage = preimage.to_xdr().ok()?;
+    Some(strkey::contract(Sha256::digest(preimage).into()))
+}
+
+pub fn from_source_account(
+    passphrase: &str,
+    source_account: &AccountId,
+    salt: [u8; 32],
+) -> Option<String> {
+    derive(HashIdPreimage::ContractIdFromSourceAccount(
+        HashIdPreimageSourceAccountContractId {
+            network_id: network_id(passphrase),
+            source_account: source_account.clone(),
+            salt: Uint256(salt),
+        },
+    ))
+}
+
+pub fn from_ed25519(passphrase: &str, key: [u8; 32], salt: [u8; 32]) -> Option<String> {
+    derive(HashIdPreimage::ContractIdFromEd25519(
+        HashIdPreimageEd25519ContractId {
+            network_id: network_id(passphrase),
+            ed25519: Uint256(key),
+            salt: Uint256(salt),
+        },
+    ))
+}
+
+pub fn from_contract(passphrase: &str, contract_id: &str, salt: [u8; 32]) -> Option<String> {
+    let contract_id = strkey::normalize_contract_id(contract_id)?;
+    let contract_id: [u8; 32] = strkey::payload(&strkey::decode(&contract_id)?)
+        .try_into()
+        .ok()?;
+
+    derive(HashIdPreimage::ContractIdFromContract(
+        HashIdPreimageContractId {
+            network_id: network_id(passphrase),
+            contract_id: Hash(contract_id),
+            salt: Uint256(salt),
+        },
+    ))
+}
+
+pub fn from_asset(passphrase: &str, asset: &Asset) -> Option<String> {
+    derive(HashIdPreimage::ContractIdFromAsset(
+        HashIdPreimageFromAsset {
+            network_id: network_id(passphrase),
+            asset: asset.clone(),
+        },
+    ))
+}
+
+/// Parses `native` or `CODE:ISSUER`.
+pub fn parse_asset(asset: &str) -> Option<Asset> {
+    if asset == "native" {
+        return Some(Asset::Native);
+    }
+
+    let (code, issuer) = asset.split_once(':')?;
+    let issuer = strkey::to_account_id(issuer)?;
+
+    match code.len() {
+        1..=4 => {
+            let mut asset_code = [0; 4];
+            asset_code[..code.len()].copy_from_slice(code.as_bytes());
+            Some(Asset::CreditAlphanum4(AlphaNum4 {
+                asset_code: AssetCode4(asset_code),
+                issuer,
+            }))
+        }
+        5..=12 => {
+            let mut asset_code = [0; 12];
+            asset_code[..code.len()].copy_from_slice(code.as_bytes());
+            Some(Asset::CreditAlphanum12(AlphaNum12 {
+                asset_code: AssetCode12(asset_code),
+                issuer,
+            }))
+        }
+        _ => None,
+    }
+}
+
+pub fn parse_bytes32(salt: &str) -> Option<[u8; 32]> {
+    hex::decode(salt.trim()).ok()?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZX";
+
+    fn key() -> [u8; 32] {
+        core::array::from_fn(|i| i as u8)
+    }
+
+    #[test]
+    fn source_account() {
+        let account = strkey::to_account_id(ACCOUNT).unwrap();
+        assert_eq!(
+            from_source_account(FUTURENET_PASSPHRASE, &account, [1; 32]).as_deref(),
+            Some("CBILN7DPI77AYFBYOZVE3P5MDOGSBVHMZKD4PVEB3KL3AOOXOV7577WM")
+        );
+        // the id depends on the network.
+        assert_ne!(
+            from_source_account("Standalone Network ; February 2017", &account, [1; 32]),
+            from_source_account(FUTURENET_PASSPHRASE, &account, [1; 32])
+        );
+    }
+
+    #[test]
+    fn ed25519() {
+        assert_eq!(
+            from_ed25519(FUTURENET_PASSPHRASE, key(), [1; 32]).as_deref(),
+            Some("CAOF4UPPGL55GZXHZVKPUOMEANNEOYOOJK4LQ33WPC6ZRXMXR5TUWANL")
+        );
+    }
+
+    #[test]
+    fn native_asset() {
+        let asset = parse_asset("native").unwrap();
+        let id = from_asset(FUTURENET_PASSPHRASE, &asset).unwrap();
+        assert_eq!(
+            strkey::to_hex(&id).as_deref(),
+            Some("d93f5c7bb0ebc4a9c8f727c5cebc4e41194d38257e1d0d910356b43bfc528813")
+        );
+    }
+
+    #[test]
+    fn credit_asset() {
+        let asset = parse_asset(&format!("USDC:{ACCOUNT}")).unwrap();
+        assert_eq!(
+            from_asset(FUTURENET_PASSPHRASE, &asset).as_deref(),
+            Some("CCOLTJGDNPNCWKIC54EPL74JFB2ZTDHDCO6BZM45XLSI2725LA55UGAU")
+        );
+        assert!(parse_asset("TOOLONGASSETCODE:GAAA").is_none());
+    }
+}
@@ -0,0 +1,107 @@
+use stellar_xdr::{
+    Int128Parts, ScObject, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScStatic, ScVal, WriteXdr,
+};
+
+use crate::{spec::Primitive, strkey, types::common::Invocation};
+
+pub const FUTURENET_RPC_URL: &str = "https://rpc-futurenet.stellar.org:443/soroban/rpc";
+// expanded by the shell, so the command runs once the variable is exported.
+pub const SECRET_KEY_PLACEHOLDER: &str = "\"$SECRET_KEY\"";
+
+// single quotes anything the shell could interpret.
+fn quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_.:/=+@".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+fn u128_parts(parts: &Int128Parts) -> u128 {
+    (parts.hi as u128) << 64 | parts.lo as u128
+}
+
+/// How the CLI parses an argument of type `ty`, `None` when it's a type the CLI can only
+/// take as XDR.
+fn arg_value(ty: &ScSpecTypeDef, val: &ScVal) -> Option<String> {
+    if let ScSpecTypeDef::BytesN(_) = ty {
+        return match val {
+            ScVal::Object(Some(ScObject::Bytes(bytes))) => Some(hex::encode(bytes.as_slice())),
+            _ => None,
+        };
+    }
+
+    let value = match (Primitive::of(ty)?, val) {
+        (Primitive::U32, ScVal::U32(n)) => n.to_string(),
+        (Primitive::I32, ScVal::I32(n)) => n.to_string(),
+        (Primitive::U64 | Primitive::I64, ScVal::U63(n)) => n.to_string(),
+        (Primitive::U64, ScVal::Object(Some(ScObject::U64(n)))) => n.to_string(),
+        (Primitive::I64, ScVal::Object(Some(ScObject::I64(n)))) => n.to_string(),
+        (Primitive::U128, ScVal::Object(Some(ScObject::U128(parts)))) => {
+            u128_parts(parts).to_string()
+        }
+        (Primitive::I128, ScVal::Object(Some(ScObject::I128(parts)))) => {
+            (u128_parts(parts) as i128).to_string()
+        }
+        (Primitive::Bool, ScVal::Static(ScStatic::True)) => String::from("true"),
+        (Primitive::Bool, ScVal::Static(ScStatic::False)) => String::from("false"),
+        (Primitive::Symbol, ScVal::Symbol(symbol)) => symbol.to_string_lossy(),
+        (Primitive::AccountId, val) => strkey::from_sc_val(val)?,
+        (Primitive::Bytes, ScVal::Object(Some(ScObject::Bytes(bytes)))) => {
+            hex::encode(bytes.as_slice())
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// A `soroban contract invoke` command line reproducing the invocation. Arguments are
+/// formatted after their type in `spec` and passed as base64 XDR when the spec is unknown
+/// or the CLI can't parse their type.
+pub fn invoke_command(
+    invocation: &Invocation,
+    spec: &[ScSpecEntry],
+    rpc_url: &str,
+    network_passphrase: &str,
+) -> String {
+    let inputs = spec.iter().find_map(|entry| match entry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 { name, inputs, .. })
+            if name.to_string_lossy() == invocation.function =>
+        {
+            Some(inputs)
+        }
+        _ => None,
+    });
+
+    let id = strkey::to_hex(&invocation.id).unwrap_or_else(|| invocation.id.clone());
+    let mut command = vec![
+        String::from("soroban contract invoke"),
+        format!("--id {id}"),
+        format!("--secret-key {SECRET_KEY_PLACEHOLDER}"),
+        format!("--rpc-url {}", quote(rpc_url)),
+        format!("--network-passphrase {}", quote(network_passphrase)),
+        format!("--fn {}", quote(&invocation.function)),
+        String::from("--"),
+    ];
+
+    for (i, arg) in invocation.args.iter().enumerate() {
+        // arguments Horizon couldn't decode can't be reproduced.
+        let arg = match arg {
+            Some(arg) => arg,
+            None => {
+                command.push(format!("--arg-xdr {}", quote("<UNKNOWN>")));
+                continue;
+            }
+        };
+        let value = inputs
+            .and_then(|inputs| inputs.get(i))
+            .and_then(|input| arg_value(&input.type_, arg));
+        command.push(match value {
+            Some(value) => format!("--arg {}", quote(&value)),
+            None => format!("--arg-xdr {}", arg.to_xdr_base64().unwrap()),
+        });
+    }
+
+    command.join(" \\\n  ")
+}
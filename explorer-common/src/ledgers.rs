@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+
+use crate::paging::parse_time;
+
+use super::types;
+
+pub async fn get_ledger(base_url: &str, sequence: u32) -> Option<types::ledger::Response> {
+    let url = format!("{base_url}/ledgers/{sequence}");
+
+    let result = reqwest::get(&url).await;
+    match result {
+        Ok(resp) => {
+            if resp.status().is_success() {
+                match resp.json::<types::ledger::Response>().await {
+                    Ok(resp) => Some(resp),
+                    Err(_) => None,
+                }
+            } else {
+                None
+            }
+        }
+        Err(_) => None,
+    }
+}
+
+pub async fn get_root(base_url: &str) -> Option<types::ledger::Root> {
+    let url = format!("{base_url}/");
+
+    let result = reqwest::get(&url).await;
+    match result {
+        Ok(resp) => {
+            if resp.status().is_success() {
+                match resp.json::<types::ledger::Root>().await {
+                    Ok(resp) => Some(resp),
+                    Err(_) => None,
+                }
+            } else {
+                None
+            }
+        }
+        Err(_) => None,
+    }
+}
+
+/// First ledger closed at or after `time`, binary searching the ledgers Horizon has history
+/// for. `None` when no ledger closed after `time` yet.
+pub async fn ledger_at(base_url: &str, time: DateTime<Utc>) -> Option<u32> {
+    let root = get_root(base_url).await?;
+    let (mut low, mut high) = (root.history_elder_ledger, root.history_latest_ledger + 1);
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let closed_at = parse_time(&get_ledger(base_url, mid).await?.closed_at)?;
+        if closed_at < time {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    if low > root.history_latest_ledger {
+        None
+    } else {
+        Some(low)
+    }
+}
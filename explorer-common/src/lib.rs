@@ -1,3 +1,18 @@
+pub mod accounts;
+pub mod builder;
+pub mod classic;
+pub mod contract_id;
+pub mod invoke;
+pub mod ledgers;
 pub mod operations;
+pub mod paging;
+pub mod scval;
+pub mod signatures;
+pub mod spec;
+pub mod storage;
+pub mod strkey;
+pub mod testgen;
 pub mod transactions;
 pub mod types;
+pub mod verify;
+pub mod wasm;
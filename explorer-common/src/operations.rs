@@ -1,32 +1,76 @@
+use std::fmt;
+
 use stellar_xdr::{ReadXdr, ScObject, ScVal};
 
 use self::utils::{build_processed_from_filter, exp_backoff_request};
 
-use super::types::{self, common::Processed};
+use super::{
+    ledgers::ledger_at,
+    paging::{parse_time, Range, Toid},
+    strkey,
+    transactions::process_tx_operations,
+    types::{
+        self,
+        common::{Event, Processed},
+    },
+};
+
+#[derive(Clone, Debug)]
+pub enum Error {
+    // Horizon couldn't be reached or kept failing for the url.
+    Request(String),
+    // the transaction of an operation couldn't be fetched.
+    Transaction(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Request(url) => write!(f, "request to {url} failed"),
+            Error::Transaction(hash) => write!(f, "couldn't fetch transaction {hash}"),
+        }
+    }
+}
 
 mod utils {
-    use super::types::{
-        self,
-        common::{Deployed, Event, Invocation, Processed},
+    use super::{
+        types::{
+            self,
+            common::{Deployed, Event, Invocation, Processed},
+            operation::HostFunction,
+        },
+        Error,
+    };
+    use serde::de::DeserializeOwned;
+
+    use crate::{
+        contract_id,
+        paging::{parse_time, Toid},
+        storage::storage_changes,
+        strkey,
+        transactions::{
+            fee_bump, get_transaction, invocation_error, memo, operation_results, preconditions,
+        },
     };
-    use crate::transactions::get_transaction;
     use stellar_xdr::{
         InvokeHostFunctionResult, LedgerFootprint, OperationResult, OperationResultTr, ReadXdr,
-        ScObject, ScVal, TransactionMeta, TransactionMetaV3, TransactionResult,
-        TransactionResultResult,
+        ScObject, ScVal, TransactionMeta, TransactionMetaV3,
     };
 
-    pub async fn exp_backoff_request(url: &str) -> types::operation::Response {
+    /// Retries failed requests with an exponential backoff, client errors aren't retried.
+    pub async fn exp_backoff_request<T: DeserializeOwned>(url: &str) -> Result<T, Error> {
         let backoff = backoff::ExponentialBackoff::default();
         backoff::future::retry(backoff, || async {
             let result = reqwest::get(url).await;
             match result {
                 Ok(resp) => {
                     if resp.status().is_success() {
-                        match resp.json::<types::operation::Response>().await {
+                        match resp.json::<T>().await {
                             Ok(resp) => Ok(resp),
                             Err(_) => Err(backoff::Error::transient(())),
                         }
+                    } else if resp.status().is_client_error() {
+                        Err(backoff::Error::permanent(()))
                     } else {
                         Err(backoff::Error::transient(()))
                     }
@@ -35,23 +79,32 @@ mod utils {
             }
         })
         .await
-        .unwrap()
+        .map_err(|_| Error::Request(url.to_string()))
     }
 
     pub async fn build_processed_from_filter(
         base_url: &str,
+        network_passphrase: Option<&str>,
         records: impl Iterator<Item = &types::operation::Record>,
         events: &mut Vec<Processed>,
-    ) {
+    ) -> Result<(), Error> {
         for r in records {
             let source_account = &r.source_account;
-            match r.function.as_deref() {
-                Some("HostFunctionHostFnInvokeContract") => {
-                    let id = if let Some(id) = r.parameters.get(0) {
+            let (toid, at) = match (Toid::parse(&r.paging_token), parse_time(&r.created_at)) {
+                (Some(toid), Some(at)) => (toid, at),
+                _ => continue,
+            };
+            let op = match r.invoke_host_function() {
+                Some(op) => op,
+                None => continue,
+            };
+            match op.function {
+                HostFunction::InvokeContract => {
+                    let id = if let Some(id) = op.parameters.get(0) {
                         if let Ok(ScVal::Object(Some(ScObject::Bytes(id)))) =
                             ScVal::from_xdr_base64(&id.value)
                         {
-                            Some(hex::encode(id))
+                            strkey::contract_from_bytes(&id)
                         } else {
                             None
                         }
@@ -59,7 +112,7 @@ mod utils {
                         None
                     };
 
-                    let function = if let Some(function) = r.parameters.get(1) {
+                    let function = if let Some(function) = op.parameters.get(1) {
                         if let Ok(ScVal::Symbol(function)) = ScVal::from_xdr_base64(&function.value)
                         {
                             Some(function.to_string_lossy())
@@ -69,7 +122,7 @@ mod utils {
                     } else {
                         None
                     };
-                    let args = r
+                    let args = op
                         .parameters
                         .iter()
                         .skip(2)
@@ -77,34 +130,29 @@ mod utils {
                         .collect::<Vec<_>>();
                     let tx = get_transaction(base_url, &r.transaction_hash)
                         .await
-                        .unwrap();
-                    let result = if let Ok(TransactionResult {
-                        result: TransactionResultResult::TxSuccess(op_results),
-                        ..
-                    }) = TransactionResult::from_xdr_base64(tx.result_xdr)
+                        .ok_or_else(|| Error::Transaction(r.transaction_hash.clone()))?;
+                    let op_results = operation_results(&tx.result_xdr);
+                    let op_result = op_results.as_ref().and_then(|results| results.get(0));
+                    let result = if let Some(OperationResult::OpInner(
+                        OperationResultTr::InvokeHostFunction(InvokeHostFunctionResult::Success(
+                            result,
+                        )),
+                    )) = op_result
                     {
-                        if let Some(OperationResult::OpInner(
-                            OperationResultTr::InvokeHostFunction(
-                                InvokeHostFunctionResult::Success(result),
-                            ),
-                        )) = op_results.get(0)
-                        {
-                            Some(result.clone())
-                        } else {
-                            None
-                        }
+                        Some(result.clone())
                     } else {
                         None
                     };
+                    let error = invocation_error(op_result);
                     let contract_events =
                         if let Ok(TransactionMeta::V3(TransactionMetaV3 { events, .. })) =
-                            TransactionMeta::from_xdr_base64(tx.result_meta_xdr)
+                            TransactionMeta::from_xdr_base64(&tx.result_meta_xdr)
                         {
                             Some(events.into())
                         } else {
                             None
                         };
-                    let footprint = if let Some(footprint) = &r.footprint {
+                    let footprint = if let Some(footprint) = &op.footprint {
                         if let Ok(footprint) = LedgerFootprint::from_xdr_base64(footprint) {
                             Some(footprint)
                         } else {
@@ -117,27 +165,34 @@ mod utils {
                         events.push(Processed {
                             source_account: source_account.to_string(),
                             tx: r.transaction_hash.clone(),
-                            at: r.created_at.clone(),
+                            at,
+                            ledger: toid.ledger,
+                            application_order: toid.application_order,
+                            paging_token: r.paging_token.clone(),
+                            fee_bump: fee_bump(&tx.envelope_xdr),
+                            memo: memo(&tx.envelope_xdr),
+                            preconditions: preconditions(&tx.envelope_xdr),
+                            storage_changes: storage_changes(
+                                &tx.result_meta_xdr,
+                                toid.operation_index.saturating_sub(1) as usize,
+                            ),
                             body: Event::Invocation(Invocation {
                                 id,
                                 function,
                                 args,
                                 result,
+                                error,
                                 footprint,
                                 events: contract_events,
                             }),
                         });
                     }
                 }
-                Some("HostFunctionHostFnCreateContractWithSourceAccount") => {
+                HostFunction::CreateContractWithSourceAccount => {
                     let tx = get_transaction(base_url, &r.transaction_hash)
                         .await
-                        .unwrap();
-                    let id = if let Ok(TransactionResult {
-                        result: TransactionResultResult::TxSuccess(op_results),
-                        ..
-                    }) = TransactionResult::from_xdr_base64(tx.result_xdr)
-                    {
+                        .ok_or_else(|| Error::Transaction(r.transaction_hash.clone()))?;
+                    let id = if let Some(op_results) = operation_results(&tx.result_xdr) {
                         if let Some(OperationResult::OpInner(
                             OperationResultTr::InvokeHostFunction(
                                 InvokeHostFunctionResult::Success(ScVal::Object(Some(
@@ -146,14 +201,14 @@ mod utils {
                             ),
                         )) = op_results.get(0)
                         {
-                            Some(hex::encode(id))
+                            strkey::contract_from_bytes(&id)
                         } else {
                             None
                         }
                     } else {
                         None
                     };
-                    let bytes = if let Some(code) = r.parameters.get(0) {
+                    let bytes = if let Some(code) = op.parameters.get(0) {
                         if let Ok(ScVal::Object(Some(ScObject::Bytes(bytes)))) =
                             ScVal::from_xdr_base64(&code.value)
                         {
@@ -164,73 +219,261 @@ mod utils {
                     } else {
                         None
                     };
+                    let salt = if let Some(salt) = op.parameters.get(1) {
+                        if let Ok(ScVal::Object(Some(ScObject::Bytes(salt)))) =
+                            ScVal::from_xdr_base64(&salt.value)
+                        {
+                            salt.as_slice().try_into().ok()
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    };
+                    let derived_id = if let (Some(passphrase), Some(salt), Some(account)) = (
+                        network_passphrase,
+                        salt,
+                        strkey::to_account_id(source_account),
+                    ) {
+                        contract_id::from_source_account(passphrase, &account, salt)
+                    } else {
+                        None
+                    };
                     if let (Some(id), Some(bytes)) = (id, bytes) {
                         events.push(Processed {
                             source_account: source_account.to_string(),
                             tx: r.transaction_hash.clone(),
-                            at: r.created_at.clone(),
-                            body: Event::Deployment(Deployed { id, bytes }),
+                            at,
+                            ledger: toid.ledger,
+                            application_order: toid.application_order,
+                            paging_token: r.paging_token.clone(),
+                            fee_bump: fee_bump(&tx.envelope_xdr),
+                            memo: memo(&tx.envelope_xdr),
+                            preconditions: preconditions(&tx.envelope_xdr),
+                            storage_changes: storage_changes(
+                                &tx.result_meta_xdr,
+                                toid.operation_index.saturating_sub(1) as usize,
+                            ),
+                            body: Event::Deployment(Deployed {
+                                id,
+                                bytes,
+                                derived_id,
+                            }),
                         });
                     }
                 }
-                _ => {}
+                HostFunction::Other => {}
             }
         }
+        Ok(())
     }
 }
 
-pub async fn get_operations(base_url: &str, url: &str) -> (Vec<Processed>, Option<String>, String) {
-    let resp = exp_backoff_request(url).await;
+pub async fn get_operations(
+    base_url: &str,
+    network_passphrase: Option<&str>,
+    url: &str,
+) -> Result<(Vec<Processed>, Option<String>, String), Error> {
+    let resp: types::operation::Response = exp_backoff_request(url).await?;
     let records = resp
         .embedded
         .records
         .iter()
-        .filter(|r| r.r#type == "invoke_host_function");
+        .filter(|r| r.invoke_host_function().is_some());
 
     let mut events: Vec<Processed> = vec![];
-    build_processed_from_filter(base_url, records, &mut events).await;
-    (
+    build_processed_from_filter(base_url, network_passphrase, records, &mut events).await?;
+    Ok((
         events,
         resp.embedded
             .records
             .first()
             .map(|r| r.paging_token.clone()),
         resp.links.next.href,
-    )
+    ))
+}
+
+fn invokes_contract(r: &types::operation::Record, contract_id: &Option<String>) -> bool {
+    let id = if let Some(id) = r.invoke_host_function().and_then(|op| op.parameters.get(0)) {
+        if let Ok(ScVal::Object(Some(ScObject::Bytes(id)))) = ScVal::from_xdr_base64(&id.value) {
+            strkey::contract_from_bytes(&id)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+    contract_id.is_some() && &id == contract_id
 }
 
 pub async fn get_contract_operations(
     base_url: &str,
+    network_passphrase: Option<&str>,
     url: &str,
     contract_id: &str,
-) -> (Vec<Processed>, Option<String>, String) {
-    let resp = exp_backoff_request(url).await;
-    let records = resp.embedded.records.iter().filter(|r| {
-        let id = if let Some(id) = r.parameters.get(0) {
-            if let Ok(ScVal::Object(Some(ScObject::Bytes(id)))) = ScVal::from_xdr_base64(&id.value)
-            {
-                Some(hex::encode(id))
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-        if r.r#type == "invoke_host_function" && id == Some(contract_id.to_string()) {
-            true
-        } else {
-            false
-        }
-    });
+) -> Result<(Vec<Processed>, Option<String>, String), Error> {
+    let contract_id = strkey::normalize_contract_id(contract_id);
+    let resp: types::operation::Response = exp_backoff_request(url).await?;
+    let records = resp
+        .embedded
+        .records
+        .iter()
+        .filter(|r| invokes_contract(r, &contract_id));
+
+    let mut events: Vec<Processed> = vec![];
+    build_processed_from_filter(base_url, network_passphrase, records, &mut events).await?;
+    Ok((
+        events,
+        resp.embedded
+            .records
+            .first()
+            .map(|r| r.paging_token.clone()),
+        resp.links.next.href,
+    ))
+}
+
+/// Invocations and deployments submitted by `account_id`, from a page of
+/// `/accounts/{account_id}/operations`.
+pub async fn get_account_operations(
+    base_url: &str,
+    network_passphrase: Option<&str>,
+    url: &str,
+    account_id: &str,
+) -> Result<(Vec<Processed>, Option<String>, String), Error> {
+    let resp: types::operation::Response = exp_backoff_request(url).await?;
+    let records = resp
+        .embedded
+        .records
+        .iter()
+        .filter(|r| r.invoke_host_function().is_some() && r.source_account == account_id);
 
     let mut events: Vec<Processed> = vec![];
-    build_processed_from_filter(base_url, records, &mut events).await;
-    (
+    build_processed_from_filter(base_url, network_passphrase, records, &mut events).await?;
+    Ok((
         events,
         resp.embedded
             .records
             .first()
             .map(|r| r.paging_token.clone()),
         resp.links.next.href,
-    )
+    ))
+}
+
+/// Same as `get_account_operations` from a page of `/accounts/{account_id}/transactions`,
+/// which already embeds the transactions' XDR and saves a request per operation.
+pub async fn get_account_transactions(
+    network_passphrase: Option<&str>,
+    url: &str,
+    account_id: &str,
+) -> Result<(Vec<Processed>, Option<String>, String), Error> {
+    let resp: types::transaction::Page = exp_backoff_request(url).await?;
+    let first = resp
+        .embedded
+        .records
+        .first()
+        .map(|tx| tx.paging_token.clone());
+
+    let events = resp
+        .embedded
+        .records
+        .into_iter()
+        .flat_map(|tx| process_tx_operations(tx, network_passphrase))
+        .filter(|p| {
+            p.source_account == account_id
+                && matches!(p.body, Event::Invocation(_) | Event::Deployment(_))
+        })
+        .collect();
+    Ok((events, first, resp.links.next.href))
+}
+
+/// Invocations and deployments in `range`, oldest first, only the ones invoking `contract_id`
+/// when set. Ranges with a lower bound are scanned forward from their first ledger until the
+/// range ends, the others backward from their upper bound until the oldest operation Horizon
+/// has.
+pub async fn get_operations_in_range(
+    base_url: &str,
+    network_passphrase: Option<&str>,
+    contract_id: Option<&str>,
+    range: Range,
+) -> Result<Vec<Processed>, Error> {
+    let from_ledger = match range.from {
+        Some(from) => match ledger_at(base_url, from).await {
+            Some(ledger) => Some(ledger.max(range.from_ledger.unwrap_or(0))),
+            None => return Ok(vec![]),
+        },
+        None => range.from_ledger,
+    };
+    // cursors are exclusive and sort before every operation of their ledger.
+    let cursor = |ledger| {
+        Toid {
+            ledger,
+            application_order: 0,
+            operation_index: 0,
+        }
+        .paging_token()
+    };
+    let ascending = from_ledger.is_some();
+    let contract_id = contract_id.map(strkey::normalize_contract_id);
+
+    let mut url = match from_ledger {
+        Some(from_ledger) => format!(
+            "{base_url}/operations?order=asc&limit=200&cursor={}",
+            cursor(from_ledger)
+        ),
+        None => {
+            // no ledger closed at or after `to` yet means the whole history is before it.
+            let to_ledger = match range.to {
+                Some(to) => match ledger_at(base_url, to).await {
+                    Some(ledger) => Some(ledger.min(range.to_ledger.unwrap_or(u32::MAX))),
+                    None => range.to_ledger,
+                },
+                None => range.to_ledger,
+            };
+            match to_ledger {
+                Some(to_ledger) => format!(
+                    "{base_url}/operations?order=desc&limit=200&cursor={}",
+                    cursor(to_ledger)
+                ),
+                None => format!("{base_url}/operations?order=desc&limit=200"),
+            }
+        }
+    };
+    let mut events: Vec<Processed> = vec![];
+    loop {
+        let resp: types::operation::Response = exp_backoff_request(&url).await?;
+        let records = resp.embedded.records.iter().filter(|r| {
+            let in_range = match (Toid::parse(&r.paging_token), parse_time(&r.created_at)) {
+                (Some(toid), Some(at)) => range.contains(toid.ledger, at),
+                _ => false,
+            };
+            let invoked = match &contract_id {
+                Some(contract_id) => invokes_contract(r, contract_id),
+                None => r.invoke_host_function().is_some(),
+            };
+            in_range && invoked
+        });
+        build_processed_from_filter(base_url, network_passphrase, records, &mut events).await?;
+
+        // an empty page means we reached the latest ledger, or the oldest one when walking
+        // backward.
+        let ended = match resp.embedded.records.last() {
+            Some(r) if ascending => {
+                match (Toid::parse(&r.paging_token), parse_time(&r.created_at)) {
+                    (Some(toid), Some(at)) => range.ended(toid.ledger, at),
+                    _ => false,
+                }
+            }
+            Some(_) => false,
+            None => true,
+        };
+        if ended {
+            break;
+        }
+        url = resp.links.next.href;
+    }
+
+    if !ascending {
+        events.reverse();
+    }
+    Ok(events)
 }
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+
+/// Total order id that Horizon uses for ids and paging tokens: the ledger sequence, the
+/// application order of the transaction in the ledger, and the 1-based index of the operation
+/// in the transaction (0 for the transaction itself).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Toid {
+    pub ledger: u32,
+    pub application_order: u32,
+    pub operation_index: u32,
+}
+
+impl Toid {
+    pub fn parse(paging_token: &str) -> Option<Self> {
+        let id = paging_token.parse::<u64>().ok()?;
+        Some(Self {
+            ledger: (id >> 32) as u32,
+            application_order: ((id >> 12) & 0xfffff) as u32,
+            operation_index: (id & 0xfff) as u32,
+        })
+    }
+
+    pub fn operation(self, index: u32) -> Self {
+        Self {
+            operation_index: index,
+            ..self
+        }
+    }
+
+    pub fn paging_token(&self) -> String {
+        (((self.ledger as u64) << 32)
+            | ((self.application_order as u64) << 12)
+            | self.operation_index as u64)
+            .to_string()
+    }
+}
+
+/// Lower bounds are inclusive and upper bounds exclusive, unset bounds are open.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Range {
+    pub from_ledger: Option<u32>,
+    pub to_ledger: Option<u32>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl Range {
+    pub fn is_bounded(&self) -> bool {
+        self.from_ledger.is_some()
+            || self.to_ledger.is_some()
+            || self.from.is_some()
+            || self.to.is_some()
+    }
+
+    pub fn contains(&self, ledger: u32, at: DateTime<Utc>) -> bool {
+        self.from_ledger.map_or(true, |from| ledger >= from)
+            && self.from.map_or(true, |from| at >= from)
+            && !self.ended(ledger, at)
+    }
+
+    /// Whether everything at or after this point is past the upper bounds.
+    pub fn ended(&self, ledger: u32, at: DateTime<Utc>) -> bool {
+        self.to_ledger.map_or(false, |to| ledger >= to) || self.to.map_or(false, |to| at >= to)
+    }
+}
+
+/// Parses Horizon's `created_at` timestamps.
+pub fn parse_time(time: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(toid: Toid) {
+        assert_eq!(Toid::parse(&toid.paging_token()), Some(toid));
+    }
+
+    #[test]
+    fn horizon_paging_token() {
+        // the first operation of the first transaction of ledger 3.
+        let toid = Toid::parse("12884905985").unwrap();
+        assert_eq!(
+            toid,
+            Toid {
+                ledger: 3,
+                application_order: 1,
+                operation_index: 1,
+            }
+        );
+        assert_eq!(toid.paging_token(), "12884905985");
+        assert_eq!(toid.operation(0).paging_token(), "12884905984");
+    }
+
+    #[test]
+    fn bounds() {
+        round_trip(Toid {
+            ledger: 0,
+            application_order: 0,
+            operation_index: 0,
+        });
+        // 12 bits of operation index and 20 bits of application order.
+        round_trip(Toid {
+            ledger: 1,
+            application_order: 1,
+            operation_index: 4095,
+        });
+        round_trip(Toid {
+            ledger: 1,
+            application_order: 0xfffff,
+            operation_index: 1,
+        });
+        round_trip(Toid {
+            ledger: u32::MAX,
+            application_order: 0xfffff,
+            operation_index: 4095,
+        });
+        assert_eq!(
+            Toid {
+                ledger: u32::MAX,
+                application_order: 0xfffff,
+                operation_index: 4095,
+            }
+            .paging_token(),
+            u64::MAX.to_string()
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let last_of_ledger = Toid {
+            ledger: 7,
+            application_order: 0xfffff,
+            operation_index: 4095,
+        };
+        let next_ledger = Toid {
+            ledger: 8,
+            application_order: 0,
+            operation_index: 0,
+        };
+        assert!(last_of_ledger < next_ledger);
+        assert!(
+            last_of_ledger.paging_token().parse::<u64>().unwrap()
+                < next_ledger.paging_token().parse::<u64>().unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_paging_token() {
+        assert_eq!(Toid::parse(""), None);
+        assert_eq!(Toid::parse("-1"), None);
+        assert_eq!(Toid::parse("now"), None);
+    }
+
+    #[test]
+    fn range() {
+        let at = parse_time("2023-01-10T12:00:00Z").unwrap();
+        let range = Range {
+            from_ledger: Some(10),
+            to_ledger: Some(20),
+            from: Some(at),
+            to: parse_time("2023-01-11T12:00:00Z"),
+        };
+
+        assert!(range.contains(10, at));
+        assert!(range.contains(19, at));
+        assert!(!range.contains(9, at));
+        assert!(!range.contains(20, at));
+        assert!(!range.contains(15, parse_time("2023-01-10T11:59:59Z").unwrap()));
+        assert!(!range.contains(15, parse_time("2023-01-11T12:00:00Z").unwrap()));
+
+        assert!(!range.ended(19, at));
+        assert!(range.ended(20, at));
+        assert!(range.ended(15, parse_time("2023-01-11T12:00:00Z").unwrap()));
+    }
+
+    #[test]
+    fn open_range() {
+        let range = Range::default();
+        let at = parse_time("2023-01-10T12:00:00+01:00").unwrap();
+        assert_eq!(at, parse_time("2023-01-10T11:00:00Z").unwrap());
+        assert!(range.contains(0, at));
+        assert!(range.contains(u32::MAX, at));
+        assert!(!range.ended(u32::MAX, at));
+    }
+}
@@ -0,0 +1,882 @@
+use std::fmt;
+
+use serde_derive::{Deserialize, Serialize};
+use stellar_xdr::{
+    Int128Parts, ScMap, ScMapEntry, ScObject, ScSpecEntry, ScSpecTypeDef, ScSpecUdtEnumV0,
+    ScSpecUdtErrorEnumV0, ScSpecUdtStructV0, ScSpecUdtUnionV0, ScStatic, ScStatus, ScVal, ScVec,
+};
+
+use crate::{spec::Primitive, strkey};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseError {
+    // byte offset of the error in the input.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl ParseError {
+    /// The input with a caret under the error.
+    pub fn pointer(&self, input: &str) -> String {
+        let column = input[..self.position.min(input.len())].chars().count();
+        format!("{input}\n{}^", " ".repeat(column))
+    }
+}
+
+// prefixes of explicitly typed values, e.g. `u64:5`.
+const PREFIXES: [&str; 11] = [
+    "u32", "i32", "u64", "i64", "u128", "i128", "bool", "sym", "bytes", "addr", "error",
+];
+
+// a quoted string or a bare word, with where it starts in the input.
+struct Token {
+    text: String,
+    position: usize,
+    quoted: bool,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    entries: &'a [ScSpecEntry],
+}
+
+fn error<T>(position: usize, message: impl Into<String>) -> Result<T, ParseError> {
+    Err(ParseError {
+        position,
+        message: message.into(),
+    })
+}
+
+fn u64_val(n: u64) -> ScVal {
+    match i64::try_from(n) {
+        Ok(n) => ScVal::U63(n),
+        Err(_) => ScVal::Object(Some(ScObject::U64(n))),
+    }
+}
+
+fn i64_val(n: i64) -> ScVal {
+    if n >= 0 {
+        ScVal::U63(n)
+    } else {
+        ScVal::Object(Some(ScObject::I64(n)))
+    }
+}
+
+fn int128_parts(n: u128) -> Int128Parts {
+    Int128Parts {
+        lo: n as u64,
+        hi: (n >> 64) as u64,
+    }
+}
+
+fn vec_val(vals: Vec<ScVal>) -> ScVal {
+    ScVal::Object(Some(ScObject::Vec(ScVec(vals.try_into().unwrap()))))
+}
+
+fn symbol(text: &str, position: usize) -> Result<ScVal, ParseError> {
+    if text.is_empty()
+        || text.len() > 10
+        || !text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return error(
+            position,
+            format!("invalid symbol `{text}`, symbols are up to 10 characters of [a-zA-Z0-9_]"),
+        );
+    }
+    Ok(ScVal::Symbol(text.as_bytes().to_vec().try_into().unwrap()))
+}
+
+fn bytes(text: &str, position: usize) -> Result<Vec<u8>, ParseError> {
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    match hex::decode(text) {
+        Ok(bytes) => Ok(bytes),
+        Err(_) => error(position, format!("invalid hex `{text}`")),
+    }
+}
+
+fn bytes_val(bytes: Vec<u8>) -> ScVal {
+    ScVal::Object(Some(ScObject::Bytes(bytes.try_into().unwrap())))
+}
+
+fn number<T: std::str::FromStr>(text: &str, position: usize, ty: &str) -> Result<T, ParseError> {
+    match text.parse() {
+        Ok(n) => Ok(n),
+        Err(_) => error(position, format!("invalid {ty} `{text}`")),
+    }
+}
+
+fn typed(prefix: &str, text: &str, position: usize) -> Result<ScVal, ParseError> {
+    let val = match prefix {
+        "u32" => ScVal::U32(number(text, position, prefix)?),
+        "i32" => ScVal::I32(number(text, position, prefix)?),
+        "u64" => u64_val(number(text, position, prefix)?),
+        "i64" => i64_val(number(text, position, prefix)?),
+        "u128" => ScVal::Object(Some(ScObject::U128(int128_parts(number(
+            text, position, prefix,
+        )?)))),
+        "i128" => ScVal::Object(Some(ScObject::I128(int128_parts(number::<i128>(
+            text, position, prefix,
+        )? as u128)))),
+        "bool" => match text {
+            "true" => ScVal::Static(ScStatic::True),
+            "false" => ScVal::Static(ScStatic::False),
+            _ => return error(position, format!("invalid bool `{text}`")),
+        },
+        "sym" => symbol(text, position)?,
+        "bytes" => bytes_val(bytes(text, position)?),
+        "addr" => match strkey::to_sc_val(text) {
+            Some(val) => val,
+            None => return error(position, format!("invalid address `{text}`")),
+        },
+        "error" => ScVal::Status(ScStatus::ContractError(number(text, position, prefix)?)),
+        _ => unreachable!(),
+    };
+    Ok(val)
+}
+
+// bare numbers take the first of u32, i32, i64, u64, i128 and u128 that fits.
+fn integer(text: &str, position: usize) -> Result<ScVal, ParseError> {
+    if let Ok(n) = text.parse::<i128>() {
+        return Ok(if let Ok(n) = u32::try_from(n) {
+            ScVal::U32(n)
+        } else if let Ok(n) = i32::try_from(n) {
+            ScVal::I32(n)
+        } else if let Ok(n) = i64::try_from(n) {
+            i64_val(n)
+        } else if let Ok(n) = u64::try_from(n) {
+            u64_val(n)
+        } else {
+            ScVal::Object(Some(ScObject::I128(int128_parts(n as u128))))
+        });
+    }
+    typed("u128", text, position)
+}
+
+fn untyped(token: &Token) -> Result<ScVal, ParseError> {
+    let text = token.text.as_str();
+    if let Some(val) = strkey::to_sc_val(text) {
+        return Ok(val);
+    }
+    if token.quoted {
+        return symbol(text, token.position);
+    }
+    match text {
+        "true" => Ok(ScVal::Static(ScStatic::True)),
+        "false" => Ok(ScVal::Static(ScStatic::False)),
+        "null" | "void" => Ok(ScVal::Static(ScStatic::Void)),
+        _ if text.starts_with("0x") => Ok(bytes_val(bytes(text, token.position)?)),
+        _ if text.starts_with(|c: char| c == '-' || c.is_ascii_digit()) => {
+            integer(text, token.position)
+        }
+        _ => symbol(text, token.position),
+    }
+}
+
+fn spec_name(entry: &ScSpecEntry) -> Option<String> {
+    match entry {
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 { name, .. })
+        | ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 { name, .. })
+        | ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 { name, .. })
+        | ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 { name, .. }) => {
+            Some(name.to_string_lossy())
+        }
+        ScSpecEntry::FunctionV0(_) => None,
+    }
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn udt(&self, name: &str, position: usize) -> Result<&'a ScSpecEntry, ParseError> {
+        match self
+            .entries
+            .iter()
+            .find(|entry| spec_name(entry).as_deref() == Some(name))
+        {
+            Some(entry) => Ok(entry),
+            None => error(position, format!("type `{name}` isn't in the spec")),
+        }
+    }
+
+    fn string(&mut self) -> Result<Token, ParseError> {
+        let position = self.pos;
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return error(position, "unterminated string"),
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => break,
+                '\\' => {
+                    let escaped = match self.peek() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        _ => return error(self.pos, "invalid escape"),
+                    };
+                    self.pos += 1;
+                    text.push(escaped);
+                }
+                c => text.push(c),
+            }
+        }
+        Ok(Token {
+            text,
+            // errors in the contents point after the opening quote.
+            position: position + 1,
+            quoted: true,
+        })
+    }
+
+    // `key` words also end at `:`, which typed values contain.
+    fn token(&mut self, key: bool) -> Result<Token, ParseError> {
+        if self.peek() == Some('"') {
+            return self.string();
+        }
+        let position = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || ",[]{}\"".contains(c) || (key && c == ':') {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+        if self.pos == position {
+            return match self.peek() {
+                Some(c) => error(position, format!("unexpected `{c}`")),
+                None => error(position, "expected a value"),
+            };
+        }
+        Ok(Token {
+            text: self.input[position..self.pos].to_string(),
+            position,
+            quoted: false,
+        })
+    }
+
+    fn value(&mut self, ty: Option<&'a ScSpecTypeDef>) -> Result<ScVal, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(ty),
+            Some('[') => self.array(ty),
+            _ => {
+                let token = self.token(false)?;
+                self.scalar(&token, ty)
+            }
+        }
+    }
+
+    // typed values win over `ty`, quoted or not, e.g. `"amount": "i128:1000"`.
+    fn scalar(&self, token: &Token, ty: Option<&ScSpecTypeDef>) -> Result<ScVal, ParseError> {
+        if let Some((prefix, text)) = token.text.split_once(':') {
+            if PREFIXES.contains(&prefix) {
+                return typed(prefix, text, token.position + prefix.len() + 1);
+            }
+        }
+        let ty = match ty {
+            Some(ty) => ty,
+            None => return untyped(token),
+        };
+
+        let (text, position) = (token.text.as_str(), token.position);
+        match ty {
+            ScSpecTypeDef::Option(option) => match text {
+                "null" | "void" if !token.quoted => Ok(ScVal::Static(ScStatic::Void)),
+                _ => self.scalar(token, Some(&option.value_type)),
+            },
+            ScSpecTypeDef::BytesN(n) => {
+                let bytes = bytes(text, position)?;
+                if bytes.len() != n.n as usize {
+                    return error(
+                        position,
+                        format!("expected {} bytes, found {}", n.n, bytes.len()),
+                    );
+                }
+                Ok(bytes_val(bytes))
+            }
+            ScSpecTypeDef::Udt(udt) => match self.udt(&udt.name.to_string_lossy(), position)? {
+                ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 { name, cases, .. }) => {
+                    match cases
+                        .iter()
+                        .find(|case| case.name.to_string_lossy() == text)
+                    {
+                        Some(case) if case.type_.is_none() => {
+                            Ok(vec_val(vec![symbol(text, position)?]))
+                        }
+                        Some(_) => error(
+                            position,
+                            format!("case `{text}` takes a value, use `[\"{text}\", value]`"),
+                        ),
+                        None => error(
+                            position,
+                            format!("`{text}` isn't a case of `{}`", name.to_string_lossy()),
+                        ),
+                    }
+                }
+                ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 { name, cases, .. }) => {
+                    match cases
+                        .iter()
+                        .find(|case| case.name.to_string_lossy() == text)
+                    {
+                        Some(case) => Ok(ScVal::U32(case.value)),
+                        None if text.starts_with(|c: char| c.is_ascii_digit()) => {
+                            Ok(ScVal::U32(number(text, position, "u32")?))
+                        }
+                        None => error(
+                            position,
+                            format!("`{text}` isn't a case of `{}`", name.to_string_lossy()),
+                        ),
+                    }
+                }
+                ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 { name, cases, .. }) => {
+                    let code = match cases
+                        .iter()
+                        .find(|case| case.name.to_string_lossy() == text)
+                    {
+                        Some(case) => case.value,
+                        None if text.starts_with(|c: char| c.is_ascii_digit()) => {
+                            number(text, position, "u32")?
+                        }
+                        None => {
+                            return error(
+                                position,
+                                format!("`{text}` isn't a case of `{}`", name.to_string_lossy()),
+                            )
+                        }
+                    };
+                    Ok(ScVal::Status(ScStatus::ContractError(code)))
+                }
+                _ => error(
+                    position,
+                    format!("expected an object for `{}`", udt.name.to_string_lossy()),
+                ),
+            },
+            ScSpecTypeDef::Vec(_) | ScSpecTypeDef::Set(_) | ScSpecTypeDef::Tuple(_) => {
+                error(position, "expected an array")
+            }
+            ScSpecTypeDef::Map(_) => error(position, "expected an object"),
+            other => match Primitive::of(other) {
+                Some(Primitive::U32) => typed("u32", text, position),
+                Some(Primitive::I32) => typed("i32", text, position),
+                Some(Primitive::U64) => typed("u64", text, position),
+                Some(Primitive::I64) => typed("i64", text, position),
+                Some(Primitive::U128) => typed("u128", text, position),
+                Some(Primitive::I128) => typed("i128", text, position),
+                Some(Primitive::Bool) => typed("bool", text, position),
+                Some(Primitive::Symbol) => typed("sym", text, position),
+                Some(Primitive::Bytes) => typed("bytes", text, position),
+                Some(Primitive::AccountId) => typed("addr", text, position),
+                _ => untyped(token),
+            },
+        }
+    }
+
+    // parses `[a, b, ...]`, `ty` gives the type of the value at each index.
+    fn list(
+        &mut self,
+        ty: impl Fn(usize) -> Option<&'a ScSpecTypeDef>,
+    ) -> Result<Vec<ScVal>, ParseError> {
+        self.pos += 1;
+        let mut vals = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(vals);
+        }
+        loop {
+            vals.push(self.value(ty(vals.len()))?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(vals);
+                }
+                _ => return error(self.pos, "expected `,` or `]`"),
+            }
+        }
+    }
+
+    fn array(&mut self, ty: Option<&'a ScSpecTypeDef>) -> Result<ScVal, ParseError> {
+        let position = self.pos;
+        match ty {
+            None => Ok(vec_val(self.list(|_| None)?)),
+            Some(ScSpecTypeDef::Option(option)) => self.array(Some(&option.value_type)),
+            Some(ScSpecTypeDef::Vec(vec)) => Ok(vec_val(self.list(|_| Some(&vec.element_type))?)),
+            Some(ScSpecTypeDef::Set(set)) => Ok(vec_val(self.list(|_| Some(&set.element_type))?)),
+            Some(ScSpecTypeDef::Tuple(tuple)) => {
+                let vals = self.list(|i| tuple.value_types.get(i))?;
+                if vals.len() != tuple.value_types.len() {
+                    return error(
+                        position,
+                        format!(
+                            "expected {} values, found {}",
+                            tuple.value_types.len(),
+                            vals.len()
+                        ),
+                    );
+                }
+                Ok(vec_val(vals))
+            }
+            Some(ScSpecTypeDef::Udt(udt)) => match self
+                .udt(&udt.name.to_string_lossy(), position)?
+            {
+                // `["Case", value]`.
+                ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 { name, cases, .. }) => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    let token = self.token(false)?;
+                    let case = match cases
+                        .iter()
+                        .find(|case| case.name.to_string_lossy() == token.text)
+                    {
+                        Some(case) => case,
+                        None => {
+                            return error(
+                                token.position,
+                                format!(
+                                    "`{}` isn't a case of `{}`",
+                                    token.text,
+                                    name.to_string_lossy()
+                                ),
+                            )
+                        }
+                    };
+                    let mut vals = vec![symbol(&token.text, token.position)?];
+                    self.skip_whitespace();
+                    if let Some(ty) = &case.type_ {
+                        if self.peek() != Some(',') {
+                            return error(self.pos, format!("case `{}` takes a value", token.text));
+                        }
+                        self.pos += 1;
+                        vals.push(self.value(Some(ty))?);
+                        self.skip_whitespace();
+                    }
+                    if self.peek() != Some(']') {
+                        return error(self.pos, "expected `]`");
+                    }
+                    self.pos += 1;
+                    Ok(vec_val(vals))
+                }
+                _ => error(
+                    position,
+                    format!("unexpected array for `{}`", udt.name.to_string_lossy()),
+                ),
+            },
+            Some(ScSpecTypeDef::Map(_)) => error(position, "expected an object"),
+            // `Val` takes anything.
+            Some(ScSpecTypeDef::Val) => Ok(vec_val(self.list(|_| None)?)),
+            Some(_) => error(position, "unexpected array"),
+        }
+    }
+
+    fn object(&mut self, ty: Option<&'a ScSpecTypeDef>) -> Result<ScVal, ParseError> {
+        let position = self.pos;
+        let ty = match ty {
+            Some(ScSpecTypeDef::Option(option)) => Some(&option.value_type),
+            ty => ty,
+        };
+        // struct fields, the other objects are maps keyed by symbols unless typed.
+        let fields = match ty {
+            Some(ScSpecTypeDef::Udt(udt)) => {
+                match self.udt(&udt.name.to_string_lossy(), position)? {
+                    ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 { fields, .. }) => Some(fields),
+                    _ => {
+                        return error(
+                            position,
+                            format!("unexpected object for `{}`", udt.name.to_string_lossy()),
+                        )
+                    }
+                }
+            }
+            Some(ScSpecTypeDef::Map(_) | ScSpecTypeDef::Val) | None => None,
+            Some(_) => return error(position, "unexpected object"),
+        };
+
+        self.pos += 1;
+        let mut entries: Vec<ScMapEntry> = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+        } else {
+            loop {
+                self.skip_whitespace();
+                let key = self.token(true)?;
+                let (key_val, val_ty) = match (ty, fields) {
+                    (_, Some(fields)) => match fields
+                        .iter()
+                        .find(|field| field.name.to_string_lossy() == key.text)
+                    {
+                        Some(field) => (symbol(&key.text, key.position)?, Some(&field.type_)),
+                        None => {
+                            return error(key.position, format!("unknown field `{}`", key.text))
+                        }
+                    },
+                    (Some(ScSpecTypeDef::Map(map)), None) => (
+                        self.scalar(&key, Some(&map.key_type))?,
+                        Some(&map.value_type),
+                    ),
+                    _ => (symbol(&key.text, key.position)?, None),
+                };
+                if entries.iter().any(|entry| entry.key == key_val) {
+                    return error(key.position, format!("duplicate key `{}`", key.text));
+                }
+
+                self.skip_whitespace();
+                if self.peek() != Some(':') {
+                    return error(self.pos, "expected `:`");
+                }
+                self.pos += 1;
+                let val = self.value(val_ty)?;
+                entries.push(ScMapEntry { key: key_val, val });
+
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(',') => self.pos += 1,
+                    Some('}') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return error(self.pos, "expected `,` or `}`"),
+                }
+            }
+        }
+
+        if let Some(fields) = fields {
+            for field in fields.iter() {
+                let name = field.name.to_string_lossy();
+                let key = symbol(&name, position)?;
+                if !entries.iter().any(|entry| entry.key == key) {
+                    return error(position, format!("missing field `{name}`"));
+                }
+            }
+        }
+        // maps are sorted by key.
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(ScVal::Object(Some(ScObject::Map(ScMap(
+            entries.try_into().unwrap(),
+        )))))
+    }
+}
+
+/// Parses a value from text, e.g. `{"to": "G...", "amount": "1000"}`, `u64:5`, `sym:transfer`
+/// or `bytes:0xdead`. When set, `ty` guides the parsing and user-defined types are looked up
+/// in `entries`, otherwise objects are maps keyed by symbols, strings are addresses, typed
+/// values or symbols and bare numbers take the smallest integer type they fit.
+pub fn parse<'a>(
+    input: &'a str,
+    ty: Option<&'a ScSpecTypeDef>,
+    entries: &'a [ScSpecEntry],
+) -> Result<ScVal, ParseError> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        entries,
+    };
+    let val = parser.value(ty)?;
+    parser.skip_whitespace();
+    if parser.pos < input.len() {
+        return error(parser.pos, "unexpected input after the value");
+    }
+    Ok(val)
+}
+
+#[cfg(test)]
+mod tests {
+    use stellar_xdr::{
+        ScSpecTypeBytesN, ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeTuple, ScSpecTypeUdt,
+        ScSpecTypeVec,
+    };
+
+    use super::*;
+
+    const ACCOUNT: &str = "GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZX";
+    const CONTRACT: &str = "CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O";
+
+    fn sym(text: &str) -> ScVal {
+        ScVal::Symbol(text.as_bytes().to_vec().try_into().unwrap())
+    }
+
+    fn map_val(entries: Vec<(ScVal, ScVal)>) -> ScVal {
+        let entries = entries
+            .into_iter()
+            .map(|(key, val)| ScMapEntry { key, val })
+            .collect::<Vec<_>>();
+        ScVal::Object(Some(ScObject::Map(ScMap(entries.try_into().unwrap()))))
+    }
+
+    fn account() -> ScVal {
+        ScVal::Object(Some(ScObject::AccountId(
+            strkey::to_account_id(ACCOUNT).unwrap(),
+        )))
+    }
+
+    fn untyped(input: &str) -> Result<ScVal, ParseError> {
+        parse(input, None, &[])
+    }
+
+    fn with_type(input: &str, ty: ScSpecTypeDef) -> Result<ScVal, ParseError> {
+        parse(input, Some(&ty), &[])
+    }
+
+    fn failure(position: usize, message: &str) -> Result<ScVal, ParseError> {
+        error(position, message)
+    }
+
+    #[test]
+    fn typed_values() {
+        assert_eq!(untyped("u32:7"), Ok(ScVal::U32(7)));
+        assert_eq!(untyped("i32:-7"), Ok(ScVal::I32(-7)));
+        assert_eq!(untyped("u64:5"), Ok(ScVal::U63(5)));
+        assert_eq!(
+            untyped("u64:18446744073709551615"),
+            Ok(ScVal::Object(Some(ScObject::U64(u64::MAX))))
+        );
+        assert_eq!(untyped("i64:5"), Ok(ScVal::U63(5)));
+        assert_eq!(
+            untyped("i64:-5"),
+            Ok(ScVal::Object(Some(ScObject::I64(-5))))
+        );
+        assert_eq!(
+            untyped("u128:340282366920938463463374607431768211455"),
+            Ok(ScVal::Object(Some(ScObject::U128(Int128Parts {
+                lo: u64::MAX,
+                hi: u64::MAX,
+            }))))
+        );
+        assert_eq!(
+            untyped("i128:-1"),
+            Ok(ScVal::Object(Some(ScObject::I128(Int128Parts {
+                lo: u64::MAX,
+                hi: u64::MAX,
+            }))))
+        );
+        assert_eq!(untyped("bool:false"), Ok(ScVal::Static(ScStatic::False)));
+        assert_eq!(untyped("sym:transfer"), Ok(sym("transfer")));
+        assert_eq!(untyped("bytes:0xdead"), Ok(bytes_val(vec![0xde, 0xad])));
+        assert_eq!(untyped("bytes:dead"), Ok(bytes_val(vec![0xde, 0xad])));
+        assert_eq!(untyped(&format!("addr:{ACCOUNT}")), Ok(account()));
+        assert_eq!(
+            untyped("error:3"),
+            Ok(ScVal::Status(ScStatus::ContractError(3)))
+        );
+    }
+
+    #[test]
+    fn nested_values() {
+        assert_eq!(
+            untyped(r#"[1, [true, "sym:a"], {"b": null}, []]"#),
+            Ok(vec_val(vec![
+                ScVal::U32(1),
+                vec_val(vec![ScVal::Static(ScStatic::True), sym("a")]),
+                map_val(vec![(sym("b"), ScVal::Static(ScStatic::Void))]),
+                vec_val(vec![]),
+            ]))
+        );
+        // maps are sorted by key whatever the input order.
+        assert_eq!(
+            untyped(r#"{"b": 1, a: {"c": "u64:2"}}"#),
+            Ok(map_val(vec![
+                (sym("a"), map_val(vec![(sym("c"), ScVal::U63(2))])),
+                (sym("b"), ScVal::U32(1)),
+            ]))
+        );
+        assert_eq!(
+            untyped(r#"{"a": 1, "a": 2}"#),
+            failure(10, "duplicate key `a`")
+        );
+    }
+
+    #[test]
+    fn quoted_and_bare_words() {
+        assert_eq!(untyped("true"), Ok(ScVal::Static(ScStatic::True)));
+        assert_eq!(untyped(r#""true""#), Ok(sym("true")));
+        assert_eq!(untyped("null"), Ok(ScVal::Static(ScStatic::Void)));
+        assert_eq!(untyped(r#""null""#), Ok(sym("null")));
+        assert_eq!(untyped("5"), Ok(ScVal::U32(5)));
+        assert_eq!(untyped(r#""5""#), Ok(sym("5")));
+        assert_eq!(untyped("-5"), Ok(ScVal::I32(-5)));
+        assert_eq!(untyped("4294967296"), Ok(ScVal::U63(4294967296)));
+        assert_eq!(untyped("0xdead"), Ok(bytes_val(vec![0xde, 0xad])));
+        assert_eq!(untyped("transfer"), Ok(sym("transfer")));
+        // typed values are honoured in strings too.
+        assert_eq!(
+            untyped(&format!(r#"{{"to": "{ACCOUNT}", "amount": "i128:1000"}}"#)),
+            Ok(map_val(vec![
+                (
+                    sym("amount"),
+                    ScVal::Object(Some(ScObject::I128(Int128Parts { lo: 1000, hi: 0 })))
+                ),
+                (sym("to"), account()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn addresses() {
+        assert_eq!(untyped(ACCOUNT), Ok(account()));
+        assert_eq!(untyped(&format!(r#""{ACCOUNT}""#)), Ok(account()));
+        assert_eq!(untyped(CONTRACT), Ok(bytes_val((0..32).collect())));
+        assert_eq!(
+            with_type(CONTRACT, ScSpecTypeDef::AccountId),
+            Ok(bytes_val((0..32).collect()))
+        );
+        assert_eq!(
+            untyped("addr:GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZY"),
+            failure(
+                5,
+                "invalid address `GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZY`"
+            )
+        );
+    }
+
+    #[test]
+    fn spec_types() {
+        assert_eq!(with_type("5", ScSpecTypeDef::U64), Ok(ScVal::U63(5)));
+        assert_eq!(
+            with_type("1000", ScSpecTypeDef::I128),
+            Ok(ScVal::Object(Some(ScObject::I128(Int128Parts {
+                lo: 1000,
+                hi: 0,
+            }))))
+        );
+        assert_eq!(with_type("5", ScSpecTypeDef::Symbol), Ok(sym("5")));
+        // typed values win over the spec.
+        assert_eq!(with_type("u64:5", ScSpecTypeDef::U32), Ok(ScVal::U63(5)));
+
+        let option = ScSpecTypeDef::Option(
+            ScSpecTypeOption {
+                value_type: ScSpecTypeDef::U64.into(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            with_type("null", option.clone()),
+            Ok(ScVal::Static(ScStatic::Void))
+        );
+        assert_eq!(with_type("5", option), Ok(ScVal::U63(5)));
+
+        let vec = ScSpecTypeDef::Vec(
+            ScSpecTypeVec {
+                element_type: ScSpecTypeDef::U64.into(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            with_type("[1, 2]", vec.clone()),
+            Ok(vec_val(vec![ScVal::U63(1), ScVal::U63(2)]))
+        );
+        assert_eq!(with_type("1", vec), failure(0, "expected an array"));
+
+        let map = ScSpecTypeDef::Map(
+            ScSpecTypeMap {
+                key_type: ScSpecTypeDef::U32.into(),
+                value_type: ScSpecTypeDef::I128.into(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            with_type(r#"{"2": 1, "1": 2}"#, map),
+            Ok(map_val(vec![
+                (
+                    ScVal::U32(1),
+                    ScVal::Object(Some(ScObject::I128(Int128Parts { lo: 2, hi: 0 })))
+                ),
+                (
+                    ScVal::U32(2),
+                    ScVal::Object(Some(ScObject::I128(Int128Parts { lo: 1, hi: 0 })))
+                ),
+            ]))
+        );
+
+        let tuple = ScSpecTypeDef::Tuple(
+            ScSpecTypeTuple {
+                value_types: vec![ScSpecTypeDef::U64, ScSpecTypeDef::Bool]
+                    .try_into()
+                    .unwrap(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            with_type("[1, true]", tuple.clone()),
+            Ok(vec_val(vec![ScVal::U63(1), ScVal::Static(ScStatic::True)]))
+        );
+        assert_eq!(
+            with_type("[1]", tuple),
+            failure(0, "expected 2 values, found 1")
+        );
+
+        let bytes_n = ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: 4 });
+        assert_eq!(
+            with_type("0xdeadbeef", bytes_n.clone()),
+            Ok(bytes_val(vec![0xde, 0xad, 0xbe, 0xef]))
+        );
+        assert_eq!(
+            with_type("0xdead", bytes_n),
+            failure(0, "expected 4 bytes, found 2")
+        );
+
+        // `Val` takes anything.
+        assert_eq!(
+            with_type(r#"[1, {"a": 2}]"#, ScSpecTypeDef::Val),
+            Ok(vec_val(vec![
+                ScVal::U32(1),
+                map_val(vec![(sym("a"), ScVal::U32(2))]),
+            ]))
+        );
+
+        let udt = ScSpecTypeDef::Udt(ScSpecTypeUdt {
+            name: "Foo".as_bytes().to_vec().try_into().unwrap(),
+        });
+        assert_eq!(
+            with_type("1", udt),
+            failure(0, "type `Foo` isn't in the spec")
+        );
+    }
+
+    #[test]
+    fn error_positions() {
+        assert_eq!(untyped("[1, u32:x]"), failure(8, "invalid u32 `x`"));
+        assert_eq!(
+            untyped(r#"{"amount": "i128:abc"}"#),
+            failure(17, "invalid i128 `abc`")
+        );
+        assert_eq!(untyped("[1, 2"), failure(5, "expected `,` or `]`"));
+        assert_eq!(
+            untyped("1 2"),
+            failure(2, "unexpected input after the value")
+        );
+        assert_eq!(untyped(r#""abc"#), failure(0, "unterminated string"));
+        assert_eq!(
+            untyped("sym:this_is_too_long"),
+            failure(
+                4,
+                "invalid symbol `this_is_too_long`, symbols are up to 10 characters of [a-zA-Z0-9_]"
+            )
+        );
+
+        let e = untyped("[1, u32:x]").unwrap_err();
+        assert_eq!(e.to_string(), "invalid u32 `x` at position 8");
+        assert_eq!(e.pointer("[1, u32:x]"), "[1, u32:x]\n        ^");
+    }
+}
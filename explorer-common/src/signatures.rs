@@ -0,0 +1,245 @@
+use ed25519_dalek::{PublicKey as Ed25519PublicKey, Signature, Verifier};
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use stellar_strkey::{ed25519, HashX, PreAuthTx, Strkey};
+use stellar_xdr::{
+    DecoratedSignature, FeeBumpTransactionEnvelope, FeeBumpTransactionInnerTx, MuxedAccount,
+    Preconditions, ReadXdr, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction, TransactionV0,
+    TransactionV0Envelope, TransactionV1Envelope, WriteXdr,
+};
+
+use crate::{accounts::get_account, contract_id::network_id, strkey, types};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignatureCheck {
+    pub hint: String,
+    // `None` when none of the known signers produced this signature.
+    pub signer: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccountThresholds {
+    pub account: String,
+    pub signed_weight: u32,
+    pub low: bool,
+    pub medium: bool,
+    pub high: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignatureReport {
+    pub hash: String,
+    pub hash_matches: bool,
+    pub signatures: Vec<SignatureCheck>,
+    pub accounts: Vec<AccountThresholds>,
+}
+
+// signatures over a single transaction hash, with the accounts that need to sign it.
+struct Signed {
+    hash: [u8; 32],
+    signatures: Vec<DecoratedSignature>,
+    accounts: Vec<String>,
+}
+
+pub fn v0_to_v1(tx: &TransactionV0) -> Transaction {
+    Transaction {
+        source_account: MuxedAccount::Ed25519(tx.source_account_ed25519.clone()),
+        fee: tx.fee,
+        seq_num: tx.seq_num.clone(),
+        cond: match &tx.time_bounds {
+            Some(time_bounds) => Preconditions::Time(time_bounds.clone()),
+            None => Preconditions::None,
+        },
+        memo: tx.memo.clone(),
+        operations: tx.operations.clone(),
+        ext: TransactionExt::V0,
+    }
+}
+
+fn payload_hash(
+    passphrase: &str,
+    tagged_transaction: TransactionSignaturePayloadTaggedTransaction,
+) -> Option<[u8; 32]> {
+    let payload = TransactionSignaturePayload {
+        network_id: network_id(passphrase),
+        tagged_transaction,
+    }
+    .to_xdr()
+    .ok()?;
+
+    Some(Sha256::digest(payload).into())
+}
+
+pub fn transaction_hash(envelope: &TransactionEnvelope, passphrase: &str) -> Option<[u8; 32]> {
+    let tagged_transaction = match envelope {
+        TransactionEnvelope::TxV0(TransactionV0Envelope { tx, .. }) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(v0_to_v1(tx))
+        }
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone())
+        }
+        TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, .. }) => {
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(tx.clone())
+        }
+    };
+
+    payload_hash(passphrase, tagged_transaction)
+}
+
+fn accounts(tx: &Transaction) -> Vec<String> {
+    let mut accounts = vec![strkey::account_id(&strkey::muxed_to_account_id(
+        &tx.source_account,
+    ))];
+
+    for op in tx.operations.iter() {
+        if let Some(source) = &op.source_account {
+            let source = strkey::account_id(&strkey::muxed_to_account_id(source));
+            if !accounts.contains(&source) {
+                accounts.push(source);
+            }
+        }
+    }
+
+    accounts
+}
+
+fn signed(envelope: &TransactionEnvelope, passphrase: &str) -> Option<Vec<Signed>> {
+    Some(match envelope {
+        TransactionEnvelope::TxV0(TransactionV0Envelope { tx, signatures }) => vec![Signed {
+            hash: transaction_hash(envelope, passphrase)?,
+            signatures: signatures.to_vec(),
+            accounts: accounts(&v0_to_v1(tx)),
+        }],
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) => vec![Signed {
+            hash: transaction_hash(envelope, passphrase)?,
+            signatures: signatures.to_vec(),
+            accounts: accounts(tx),
+        }],
+        TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, signatures }) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &tx.inner_tx;
+            vec![
+                Signed {
+                    hash: transaction_hash(envelope, passphrase)?,
+                    signatures: signatures.to_vec(),
+                    accounts: vec![strkey::account_id(&strkey::muxed_to_account_id(
+                        &tx.fee_source,
+                    ))],
+                },
+                Signed {
+                    hash: payload_hash(
+                        passphrase,
+                        TransactionSignaturePayloadTaggedTransaction::Tx(inner.tx.clone()),
+                    )?,
+                    signatures: inner.signatures.to_vec(),
+                    accounts: accounts(&inner.tx),
+                },
+            ]
+        }
+    })
+}
+
+/// Finds which of the candidate signers (`G...` or `X...` strkeys) produced the signature.
+pub fn signer_of(
+    hash: &[u8; 32],
+    signature: &DecoratedSignature,
+    candidates: &[String],
+) -> Option<String> {
+    let hint = &signature.hint.0[..];
+    let signature_bytes = signature.signature.0.as_slice();
+
+    for candidate in candidates {
+        match strkey::decode(candidate) {
+            Some(Strkey::PublicKeyEd25519(ed25519::PublicKey(key))) if &key[28..] == hint => {
+                if let (Ok(public), Ok(sig)) = (
+                    Ed25519PublicKey::from_bytes(&key),
+                    Signature::try_from(signature_bytes),
+                ) {
+                    if public.verify(hash, &sig).is_ok() {
+                        return Some(candidate.clone());
+                    }
+                }
+            }
+            Some(Strkey::HashX(HashX(x))) if &x[28..] == hint => {
+                if Sha256::digest(signature_bytes).as_slice() == &x[..] {
+                    return Some(candidate.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn has_signed(key: &str, hash: &[u8; 32], checks: &[SignatureCheck]) -> bool {
+    match strkey::decode(key) {
+        Some(Strkey::PreAuthTx(PreAuthTx(preauth))) => &preauth == hash,
+        _ => checks
+            .iter()
+            .any(|check| check.signer.as_deref() == Some(key)),
+    }
+}
+
+/// Recomputes the transaction hash and checks each signature against the current
+/// signers of the accounts involved. Signers may have changed since the transaction
+/// was submitted, so thresholds are only an indication.
+pub async fn check_signatures(
+    base_url: &str,
+    tx: &types::transaction::Response,
+    passphrase: &str,
+) -> Option<SignatureReport> {
+    let envelope = TransactionEnvelope::from_xdr_base64(&tx.envelope_xdr).ok()?;
+    let hash = hex::encode(transaction_hash(&envelope, passphrase)?);
+
+    let mut report = SignatureReport {
+        hash_matches: hash == tx.hash,
+        hash,
+        signatures: vec![],
+        accounts: vec![],
+    };
+
+    for group in signed(&envelope, passphrase)? {
+        let mut candidates = vec![];
+        let mut horizon_accounts = vec![];
+        for account in &group.accounts {
+            match get_account(base_url, account).await {
+                Some(resp) => {
+                    candidates.extend(resp.signers.iter().map(|s| s.key.clone()));
+                    horizon_accounts.push(resp);
+                }
+                None => candidates.push(account.clone()),
+            }
+        }
+
+        let checks = group
+            .signatures
+            .iter()
+            .map(|signature| SignatureCheck {
+                hint: hex::encode(signature.hint.0),
+                signer: signer_of(&group.hash, signature, &candidates),
+            })
+            .collect::<Vec<_>>();
+
+        for account in horizon_accounts {
+            let signed_weight = account
+                .signers
+                .iter()
+                .filter(|s| has_signed(&s.key, &group.hash, &checks))
+                .map(|s| s.weight)
+                .sum();
+
+            report.accounts.push(AccountThresholds {
+                account: account.account_id,
+                signed_weight,
+                low: signed_weight >= account.thresholds.low_threshold,
+                medium: signed_weight >= account.thresholds.med_threshold,
+                high: signed_weight >= account.thresholds.high_threshold,
+            });
+        }
+
+        report.signatures.extend(checks);
+    }
+
+    Some(report)
+}
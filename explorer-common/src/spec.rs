@@ -0,0 +1,420 @@
+use serde_derive::{Deserialize, Serialize};
+use stellar_xdr::{
+    ContractEventBody, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0,
+    ScSpecUdtErrorEnumV0, ScSpecUdtStructV0, ScSpecUdtUnionV0, ScStatus, ScVal,
+};
+
+use crate::{types::common::Invocation, wasm};
+
+pub fn from_wasm(wasm: &[u8]) -> Option<Vec<ScSpecEntry>> {
+    Some(wasm::inspect(wasm)?.spec)
+}
+
+/// Spec types without parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Primitive {
+    Val,
+    U32,
+    I32,
+    U64,
+    I64,
+    U128,
+    I128,
+    Bool,
+    Symbol,
+    Bitset,
+    Status,
+    Bytes,
+    Invoker,
+    AccountId,
+}
+
+impl Primitive {
+    /// `None` for types with parameters, user-defined types included.
+    pub fn of(ty: &ScSpecTypeDef) -> Option<Self> {
+        Some(match ty {
+            ScSpecTypeDef::Val => Primitive::Val,
+            ScSpecTypeDef::U32 => Primitive::U32,
+            ScSpecTypeDef::I32 => Primitive::I32,
+            ScSpecTypeDef::U64 => Primitive::U64,
+            ScSpecTypeDef::I64 => Primitive::I64,
+            ScSpecTypeDef::U128 => Primitive::U128,
+            ScSpecTypeDef::I128 => Primitive::I128,
+            ScSpecTypeDef::Bool => Primitive::Bool,
+            ScSpecTypeDef::Symbol => Primitive::Symbol,
+            ScSpecTypeDef::Bitset => Primitive::Bitset,
+            ScSpecTypeDef::Status => Primitive::Status,
+            ScSpecTypeDef::Bytes => Primitive::Bytes,
+            ScSpecTypeDef::Invoker => Primitive::Invoker,
+            ScSpecTypeDef::AccountId => Primitive::AccountId,
+            _ => return None,
+        })
+    }
+}
+
+pub fn rust_type(ty: &ScSpecTypeDef) -> String {
+    match ty {
+        ScSpecTypeDef::Option(option) => format!("Option<{}>", rust_type(&option.value_type)),
+        ScSpecTypeDef::Result(result) => format!(
+            "Result<{}, {}>",
+            rust_type(&result.ok_type),
+            rust_type(&result.error_type)
+        ),
+        ScSpecTypeDef::Vec(vec) => format!("Vec<{}>", rust_type(&vec.element_type)),
+        ScSpecTypeDef::Set(set) => format!("Set<{}>", rust_type(&set.element_type)),
+        ScSpecTypeDef::Map(map) => format!(
+            "Map<{}, {}>",
+            rust_type(&map.key_type),
+            rust_type(&map.value_type)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => format!(
+            "({})",
+            tuple
+                .value_types
+                .iter()
+                .map(rust_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::BytesN(bytes) => format!("BytesN<{}>", bytes.n),
+        ScSpecTypeDef::Udt(udt) => udt.name.to_string_lossy(),
+        other => String::from(match Primitive::of(other) {
+            Some(Primitive::U32) => "u32",
+            Some(Primitive::I32) => "i32",
+            Some(Primitive::U64) => "u64",
+            Some(Primitive::I64) => "i64",
+            Some(Primitive::U128) => "u128",
+            Some(Primitive::I128) => "i128",
+            Some(Primitive::Bool) => "bool",
+            Some(Primitive::Symbol) => "Symbol",
+            Some(Primitive::Bitset) => "Bitset",
+            Some(Primitive::Status) => "Status",
+            Some(Primitive::Bytes) => "Bytes",
+            Some(Primitive::Invoker) => "Invoker",
+            Some(Primitive::AccountId) => "AccountId",
+            Some(Primitive::Val) | None => "RawVal",
+        }),
+    }
+}
+
+pub fn typescript_type(ty: &ScSpecTypeDef) -> String {
+    match ty {
+        ScSpecTypeDef::Option(option) => {
+            format!("{} | undefined", typescript_type(&option.value_type))
+        }
+        // errors are thrown.
+        ScSpecTypeDef::Result(result) => typescript_type(&result.ok_type),
+        ScSpecTypeDef::Vec(vec) => format!("Array<{}>", typescript_type(&vec.element_type)),
+        ScSpecTypeDef::Set(set) => format!("Set<{}>", typescript_type(&set.element_type)),
+        ScSpecTypeDef::Map(map) => format!(
+            "Map<{}, {}>",
+            typescript_type(&map.key_type),
+            typescript_type(&map.value_type)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => format!(
+            "[{}]",
+            tuple
+                .value_types
+                .iter()
+                .map(typescript_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::BytesN(_) => String::from("Buffer"),
+        ScSpecTypeDef::Udt(udt) => udt.name.to_string_lossy(),
+        other => String::from(match Primitive::of(other) {
+            Some(Primitive::U32 | Primitive::I32) => "number",
+            Some(Primitive::U64 | Primitive::I64 | Primitive::U128 | Primitive::I128) => "bigint",
+            Some(Primitive::Bool) => "boolean",
+            Some(Primitive::Symbol | Primitive::AccountId) => "string",
+            Some(Primitive::Bytes) => "Buffer",
+            _ => "any",
+        }),
+    }
+}
+
+// `first` is an extra leading parameter, such as the env.
+fn signature(function: &ScSpecFunctionV0, first: Option<&str>) -> String {
+    let inputs = first
+        .map(String::from)
+        .into_iter()
+        .chain(function.inputs.iter().map(|input| {
+            format!(
+                "{}: {}",
+                input.name.to_string_lossy(),
+                rust_type(&input.type_)
+            )
+        }))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let output = match function.outputs.iter().map(rust_type).collect::<Vec<_>>() {
+        outputs if outputs.is_empty() => String::new(),
+        outputs if outputs.len() == 1 => format!(" -> {}", outputs[0]),
+        outputs => format!(" -> ({})", outputs.join(", ")),
+    };
+
+    format!("fn {}({inputs}){output}", function.name.to_string_lossy())
+}
+
+/// Readable listing of the functions and types of a contract.
+pub fn interface(entries: &[ScSpecEntry]) -> String {
+    let mut functions = String::new();
+    let mut types = String::new();
+
+    for entry in entries {
+        match entry {
+            ScSpecEntry::FunctionV0(function) => {
+                functions += &format!("{}\n", signature(function, None));
+            }
+            ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 { name, fields, .. }) => {
+                types += &format!("struct {} {{\n", name.to_string_lossy());
+                for field in fields.iter() {
+                    types += &format!(
+                        "    {}: {},\n",
+                        field.name.to_string_lossy(),
+                        rust_type(&field.type_)
+                    );
+                }
+                types += "}\n";
+            }
+            ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 { name, cases, .. }) => {
+                types += &format!("union {} {{\n", name.to_string_lossy());
+                for case in cases.iter() {
+                    match &case.type_ {
+                        Some(ty) => {
+                            types += &format!(
+                                "    {}({}),\n",
+                                case.name.to_string_lossy(),
+                                rust_type(ty)
+                            )
+                        }
+                        None => types += &format!("    {},\n", case.name.to_string_lossy()),
+                    }
+                }
+                types += "}\n";
+            }
+            ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 { name, cases, .. }) => {
+                types += &format!("enum {} {{\n", name.to_string_lossy());
+                for case in cases.iter() {
+                    types += &format!("    {} = {},\n", case.name.to_string_lossy(), case.value);
+                }
+                types += "}\n";
+            }
+            ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 { name, cases, .. }) => {
+                types += &format!("error {} {{\n", name.to_string_lossy());
+                for case in cases.iter() {
+                    types += &format!("    {} = {},\n", case.name.to_string_lossy(), case.value);
+                }
+                types += "}\n";
+            }
+        }
+    }
+
+    format!("// functions\n{functions}\n// types\n{types}")
+}
+
+/// Type declarations and a client trait to use the contract from a Rust crate.
+pub fn rust_declarations(entries: &[ScSpecEntry]) -> String {
+    let mut out = String::from("use soroban_sdk::*;\n\n");
+    let mut functions = vec![];
+
+    for entry in entries {
+        match entry {
+            ScSpecEntry::FunctionV0(function) => {
+                functions.push(signature(function, Some("env: Env")))
+            }
+            ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 { name, fields, .. }) => {
+                out += "#[contracttype]\n#[derive(Clone, Debug, Eq, PartialEq)]\n";
+                out += &format!("pub struct {} {{\n", name.to_string_lossy());
+                for field in fields.iter() {
+                    out += &format!(
+                        "    pub {}: {},\n",
+                        field.name.to_string_lossy(),
+                        rust_type(&field.type_)
+                    );
+                }
+                out += "}\n\n";
+            }
+            ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 { name, cases, .. }) => {
+                out += "#[contracttype]\n#[derive(Clone, Debug, Eq, PartialEq)]\n";
+                out += &format!("pub enum {} {{\n", name.to_string_lossy());
+                for case in cases.iter() {
+                    match &case.type_ {
+                        Some(ty) => {
+                            out += &format!(
+                                "    {}({}),\n",
+                                case.name.to_string_lossy(),
+                                rust_type(ty)
+                            )
+                        }
+                        None => out += &format!("    {},\n", case.name.to_string_lossy()),
+                    }
+                }
+                out += "}\n\n";
+            }
+            ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 { name, cases, .. }) => {
+                out += "#[contracttype]\n#[derive(Clone, Copy, Debug, Eq, PartialEq)]\n";
+                out += &format!("#[repr(u32)]\npub enum {} {{\n", name.to_string_lossy());
+                for case in cases.iter() {
+                    out += &format!("    {} = {},\n", case.name.to_string_lossy(), case.value);
+                }
+                out += "}\n\n";
+            }
+            ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 { name, cases, .. }) => {
+                out += "#[contracterror]\n#[derive(Clone, Copy, Debug, Eq, PartialEq)]\n";
+                out += &format!("#[repr(u32)]\npub enum {} {{\n", name.to_string_lossy());
+                for case in cases.iter() {
+                    out += &format!("    {} = {},\n", case.name.to_string_lossy(), case.value);
+                }
+                out += "}\n\n";
+            }
+        }
+    }
+
+    out += "pub trait Contract {\n";
+    for function in functions {
+        out += &format!("    {function};\n");
+    }
+    out += "}\n";
+    out
+}
+
+/// TypeScript types and a client interface, following the JS SDK's value conversions.
+pub fn typescript_declarations(entries: &[ScSpecEntry]) -> String {
+    let mut out = String::new();
+    let mut functions = vec![];
+
+    for entry in entries {
+        match entry {
+            ScSpecEntry::FunctionV0(function) => {
+                let inputs = function
+                    .inputs
+                    .iter()
+                    .map(|input| {
+                        format!(
+                            "{}: {}",
+                            input.name.to_string_lossy(),
+                            typescript_type(&input.type_)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let output = match function.outputs.get(0) {
+                    Some(ty) => typescript_type(ty),
+                    None => String::from("void"),
+                };
+                functions.push(format!(
+                    "{}({inputs}): Promise<{output}>",
+                    function.name.to_string_lossy()
+                ));
+            }
+            ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 { name, fields, .. }) => {
+                out += &format!("export interface {} {{\n", name.to_string_lossy());
+                for field in fields.iter() {
+                    out += &format!(
+                        "  {}: {};\n",
+                        field.name.to_string_lossy(),
+                        typescript_type(&field.type_)
+                    );
+                }
+                out += "}\n\n";
+            }
+            ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 { name, cases, .. }) => {
+                let cases = cases
+                    .iter()
+                    .map(|case| match &case.type_ {
+                        Some(ty) => format!(
+                            "{{ tag: \"{}\"; value: {} }}",
+                            case.name.to_string_lossy(),
+                            typescript_type(ty)
+                        ),
+                        None => format!("{{ tag: \"{}\" }}", case.name.to_string_lossy()),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n  | ");
+                out += &format!("export type {} =\n  | {cases};\n\n", name.to_string_lossy());
+            }
+            ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 { name, cases, .. }) => {
+                out += &format!("export enum {} {{\n", name.to_string_lossy());
+                for case in cases.iter() {
+                    out += &format!("  {} = {},\n", case.name.to_string_lossy(), case.value);
+                }
+                out += "}\n\n";
+            }
+            ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 { name, cases, .. }) => {
+                out += &format!("export enum {} {{\n", name.to_string_lossy());
+                for case in cases.iter() {
+                    out += &format!("  {} = {},\n", case.name.to_string_lossy(), case.value);
+                }
+                out += "}\n\n";
+            }
+        }
+    }
+
+    out += "export interface Contract {\n";
+    for function in functions {
+        out += &format!("  {function};\n");
+    }
+    out += "}\n";
+    out
+}
+
+/// Variant of an error enum of the spec, matched by its code.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContractError {
+    pub code: u32,
+    // name of the error enum.
+    pub enum_name: String,
+    pub name: String,
+}
+
+/// Code of a contract error, the status contracts fail with.
+pub fn error_code(val: &ScVal) -> Option<u32> {
+    match val {
+        ScVal::Status(ScStatus::ContractError(code)) => Some(*code),
+        _ => None,
+    }
+}
+
+/// Maps a contract error code to the variant of the spec's error enums, the spec
+/// doesn't carry doc strings at this version of the XDR.
+pub fn contract_error(entries: &[ScSpecEntry], code: u32) -> Option<ContractError> {
+    entries.iter().find_map(|entry| match entry {
+        ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 { name, cases, .. }) => cases
+            .iter()
+            .find(|case| case.value == code)
+            .map(|case| ContractError {
+                code,
+                enum_name: name.to_string_lossy(),
+                name: case.name.to_string_lossy(),
+            }),
+        _ => None,
+    })
+}
+
+/// Shown next to failed invocations: at this version of the XDR a failed
+/// `InvokeHostFunctionResult` is a bare `Malformed` or `Trapped`, without the status the
+/// contract failed with, and failed transactions keep no events.
+pub const UNKNOWN_FAILURE_CODE: &str =
+    "the contract error code of failed invocations isn't recorded at this XDR version";
+
+/// Contract errors raised by an invocation, from its result and the data of its events.
+/// Failed invocations have neither, see [`UNKNOWN_FAILURE_CODE`].
+pub fn invocation_errors(entries: &[ScSpecEntry], invocation: &Invocation) -> Vec<ContractError> {
+    let mut codes: Vec<u32> = vec![];
+    if let Some(code) = invocation.result.as_ref().and_then(error_code) {
+        codes.push(code);
+    }
+    for event in invocation.events.iter().flatten() {
+        let ContractEventBody::V0(body) = &event.body;
+        if let Some(code) = error_code(&body.data) {
+            if !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+    }
+    codes
+        .into_iter()
+        .filter_map(|code| contract_error(entries, code))
+        .collect()
+}
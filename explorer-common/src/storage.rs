@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+
+use serde_derive::{Deserialize, Serialize};
+use stellar_xdr::{
+    ContractDataEntry, Hash, LedgerEntryChange, LedgerEntryData, LedgerKey, LedgerKeyContractData,
+    ReadXdr, ScStatic, ScVal, TransactionMeta, TransactionMetaV3,
+};
+
+use crate::{
+    paging::Toid,
+    strkey,
+    types::common::{Processed, Storage, StorageChange},
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub storage: Storage,
+    pub key: ScVal,
+    pub val: ScVal,
+    pub last_modified_ledger: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeyChange {
+    pub ledger: u32,
+    pub tx: String,
+    // `None` when the entry was removed.
+    pub val: Option<ScVal>,
+}
+
+fn storage_change(contract_id: &Hash, key: &ScVal, val: Option<ScVal>) -> StorageChange {
+    let storage = if key == &ScVal::Static(ScStatic::LedgerKeyContractCode) {
+        Storage::Instance
+    } else {
+        Storage::Persistent
+    };
+
+    StorageChange {
+        contract_id: strkey::contract(contract_id.0),
+        storage,
+        key: key.clone(),
+        val,
+    }
+}
+
+/// Contract data created, updated or removed by the `op_index`th operation of a transaction.
+pub fn storage_changes(result_meta_xdr: &str, op_index: usize) -> Vec<StorageChange> {
+    let operations = match TransactionMeta::from_xdr_base64(result_meta_xdr) {
+        Ok(TransactionMeta::V3(TransactionMetaV3 { operations, .. })) => operations,
+        _ => return vec![],
+    };
+    let op_meta = match operations.get(op_index) {
+        Some(op_meta) => op_meta,
+        None => return vec![],
+    };
+
+    op_meta
+        .changes
+        .0
+        .iter()
+        .filter_map(|change| match change {
+            LedgerEntryChange::Created(entry) | LedgerEntryChange::Updated(entry) => {
+                match &entry.data {
+                    LedgerEntryData::ContractData(ContractDataEntry {
+                        contract_id,
+                        key,
+                        val,
+                    }) => Some(storage_change(contract_id, key, Some(val.clone()))),
+                    _ => None,
+                }
+            }
+            LedgerEntryChange::Removed(LedgerKey::ContractData(LedgerKeyContractData {
+                contract_id,
+                key,
+            })) => Some(storage_change(contract_id, key, None)),
+            _ => None,
+        })
+        .collect()
+}
+
+// replays in the order operations were applied, whatever order the records come in.
+fn ordered(processed: &[Processed]) -> Vec<&Processed> {
+    let mut ordered = processed.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|p| Toid::parse(&p.paging_token));
+    ordered
+}
+
+/// Storage of `contract_id` once `ledger` closed, replaying the changes recorded in
+/// `processed`. Entries last written before the first record aren't known.
+pub fn snapshot(contract_id: &str, processed: &[Processed], ledger: u32) -> Vec<Entry> {
+    let mut entries: BTreeMap<ScVal, Entry> = BTreeMap::new();
+
+    for p in ordered(processed) {
+        if p.ledger > ledger {
+            break;
+        }
+
+        for change in &p.storage_changes {
+            if change.contract_id != contract_id {
+                continue;
+            }
+            match &change.val {
+                Some(val) => {
+                    entries.insert(
+                        change.key.clone(),
+                        Entry {
+                            storage: change.storage,
+                            key: change.key.clone(),
+                            val: val.clone(),
+                            last_modified_ledger: p.ledger,
+                        },
+                    );
+                }
+                None => {
+                    entries.remove(&change.key);
+                }
+            }
+        }
+    }
+
+    entries.into_values().collect()
+}
+
+/// Every recorded write to `key` in the storage of `contract_id`, oldest first.
+pub fn key_history(contract_id: &str, processed: &[Processed], key: &ScVal) -> Vec<KeyChange> {
+    ordered(processed)
+        .into_iter()
+        .flat_map(|p| {
+            p.storage_changes
+                .iter()
+                .filter(|change| change.contract_id == contract_id && &change.key == key)
+                .map(|change| KeyChange {
+                    ledger: p.ledger,
+                    tx: p.tx.clone(),
+                    val: change.val.clone(),
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,297 @@
+use serde_derive::{Deserialize, Serialize};
+use stellar_strkey::{ed25519, Contract, HashX, PreAuthTx, Strkey};
+use stellar_xdr::{
+    AccountId, MuxedAccount, PublicKey, ReadXdr, ScObject, ScVal, SignerKey,
+    SignerKeyEd25519SignedPayload, Uint256, WriteXdr,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Kind {
+    Account,
+    Contract,
+    Seed,
+    Muxed,
+    PreAuthTx,
+    HashX,
+    SignedPayload,
+}
+
+impl Kind {
+    pub fn of(strkey: &Strkey) -> Self {
+        match strkey {
+            Strkey::PublicKeyEd25519(_) => Kind::Account,
+            Strkey::Contract(_) => Kind::Contract,
+            Strkey::PrivateKeyEd25519(_) => Kind::Seed,
+            Strkey::MuxedAccountEd25519(_) => Kind::Muxed,
+            Strkey::PreAuthTx(_) => Kind::PreAuthTx,
+            Strkey::HashX(_) => Kind::HashX,
+            Strkey::SignedPayloadEd25519(_) => Kind::SignedPayload,
+        }
+    }
+
+    pub fn prefix(&self) -> char {
+        match self {
+            Kind::Account => 'G',
+            Kind::Contract => 'C',
+            Kind::Seed => 'S',
+            Kind::Muxed => 'M',
+            Kind::PreAuthTx => 'T',
+            Kind::HashX => 'X',
+            Kind::SignedPayload => 'P',
+        }
+    }
+}
+
+/// Decodes any strkey, `None` if the version byte, length or checksum is invalid.
+pub fn decode(strkey: &str) -> Option<Strkey> {
+    Strkey::from_string(strkey.trim()).ok()
+}
+
+pub fn kind(strkey: &str) -> Option<Kind> {
+    decode(strkey).map(|strkey| Kind::of(&strkey))
+}
+
+/// Raw payload of the strkey. Muxed accounts are the ed25519 key followed by the
+/// big-endian id, signed payloads are the ed25519 key followed by the payload.
+pub fn payload(strkey: &Strkey) -> Vec<u8> {
+    match strkey {
+        Strkey::PublicKeyEd25519(ed25519::PublicKey(key))
+        | Strkey::PrivateKeyEd25519(ed25519::PrivateKey(key))
+        | Strkey::PreAuthTx(PreAuthTx(key))
+        | Strkey::HashX(HashX(key))
+        | Strkey::Contract(Contract(key)) => key.to_vec(),
+        Strkey::MuxedAccountEd25519(ed25519::MuxedAccount { ed25519, id }) => {
+            [&ed25519[..], &id.to_be_bytes()[..]].concat()
+        }
+        Strkey::SignedPayloadEd25519(ed25519::SignedPayload { ed25519, payload }) => {
+            [&ed25519[..], &payload[..]].concat()
+        }
+    }
+}
+
+pub fn from_payload(kind: Kind, payload: &[u8]) -> Option<Strkey> {
+    let key = |bytes: &[u8]| -> Option<[u8; 32]> { bytes.try_into().ok() };
+
+    Some(match kind {
+        Kind::Account => Strkey::PublicKeyEd25519(ed25519::PublicKey(key(payload)?)),
+        Kind::Contract => Strkey::Contract(Contract(key(payload)?)),
+        Kind::Seed => Strkey::PrivateKeyEd25519(ed25519::PrivateKey(key(payload)?)),
+        Kind::PreAuthTx => Strkey::PreAuthTx(PreAuthTx(key(payload)?)),
+        Kind::HashX => Strkey::HashX(HashX(key(payload)?)),
+        Kind::Muxed => {
+            if payload.len() != 40 {
+                return None;
+            }
+            Strkey::MuxedAccountEd25519(ed25519::MuxedAccount {
+                ed25519: key(&payload[..32])?,
+                id: u64::from_be_bytes(payload[32..].try_into().ok()?),
+            })
+        }
+        Kind::SignedPayload => {
+            if payload.len() < 32 || payload.len() > 32 + 64 {
+                return None;
+            }
+            Strkey::SignedPayloadEd25519(ed25519::SignedPayload {
+                ed25519: key(&payload[..32])?,
+                payload: payload[32..].to_vec(),
+            })
+        }
+    })
+}
+
+pub fn to_hex(strkey: &str) -> Option<String> {
+    decode(strkey).map(|strkey| hex::encode(payload(&strkey)))
+}
+
+pub fn from_hex(kind: Kind, encoded: &str) -> Option<String> {
+    let payload = hex::decode(encoded.trim()).ok()?;
+    from_payload(kind, &payload).map(|strkey| strkey.to_string())
+}
+
+pub fn account(key: [u8; 32]) -> String {
+    ed25519::PublicKey(key).to_string()
+}
+
+pub fn contract(id: [u8; 32]) -> String {
+    Contract(id).to_string()
+}
+
+/// Contract ids are carried as 32 bytes objects in host function parameters and results.
+pub fn contract_from_bytes(id: &[u8]) -> Option<String> {
+    id.try_into().ok().map(contract)
+}
+
+/// Accepts a contract id either as a `C...` strkey or as 32 hex-encoded bytes.
+pub fn normalize_contract_id(id: &str) -> Option<String> {
+    match decode(id) {
+        Some(Strkey::Contract(Contract(id))) => Some(contract(id)),
+        Some(_) => None,
+        None => contract_from_bytes(&hex::decode(id.trim()).ok()?),
+    }
+}
+
+pub fn account_id(account_id: &AccountId) -> String {
+    let AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))) = account_id;
+    account(*key)
+}
+
+pub fn to_account_id(strkey: &str) -> Option<AccountId> {
+    match decode(strkey)? {
+        Strkey::PublicKeyEd25519(ed25519::PublicKey(key)) => {
+            Some(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))))
+        }
+        _ => None,
+    }
+}
+
+pub fn muxed_to_account_id(account: &MuxedAccount) -> AccountId {
+    let key = match account {
+        MuxedAccount::Ed25519(key) => key.clone(),
+        MuxedAccount::MuxedEd25519(muxed) => muxed.ed25519.clone(),
+    };
+    AccountId(PublicKey::PublicKeyTypeEd25519(key))
+}
+
+pub fn muxed_account(account: &MuxedAccount) -> String {
+    match account {
+        MuxedAccount::Ed25519(Uint256(key)) => self::account(*key),
+        MuxedAccount::MuxedEd25519(muxed) => ed25519::MuxedAccount {
+            ed25519: muxed.ed25519.0,
+            id: muxed.id,
+        }
+        .to_string(),
+    }
+}
+
+pub fn signer_key(key: &SignerKey) -> String {
+    match key {
+        SignerKey::Ed25519(Uint256(key)) => account(*key),
+        SignerKey::PreAuthTx(Uint256(hash)) => Strkey::PreAuthTx(PreAuthTx(*hash)).to_string(),
+        SignerKey::HashX(Uint256(hash)) => Strkey::HashX(HashX(*hash)).to_string(),
+        SignerKey::Ed25519SignedPayload(SignerKeyEd25519SignedPayload { ed25519, payload }) => {
+            ed25519::SignedPayload {
+                ed25519: ed25519.0,
+                payload: payload.to_vec(),
+            }
+            .to_string()
+        }
+    }
+}
+
+/// Addresses are either accounts (`ScObject::AccountId`) or contracts (32 bytes
+/// `ScObject::Bytes`).
+pub fn to_sc_val(strkey: &str) -> Option<ScVal> {
+    match decode(strkey)? {
+        Strkey::PublicKeyEd25519(ed25519::PublicKey(key)) => Some(ScVal::Object(Some(
+            ScObject::AccountId(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key)))),
+        ))),
+        Strkey::Contract(Contract(id)) => Some(ScVal::Object(Some(ScObject::Bytes(
+            id.to_vec().try_into().ok()?,
+        )))),
+        _ => None,
+    }
+}
+
+pub fn from_sc_val(val: &ScVal) -> Option<String> {
+    match val {
+        ScVal::Object(Some(ScObject::AccountId(id))) => Some(account_id(id)),
+        ScVal::Object(Some(ScObject::Bytes(id))) => contract_from_bytes(id),
+        _ => None,
+    }
+}
+
+pub fn to_sc_val_xdr(strkey: &str) -> Option<String> {
+    to_sc_val(strkey)?.to_xdr_base64().ok()
+}
+
+pub fn from_sc_val_xdr(xdr: &str) -> Option<String> {
+    from_sc_val(&ScVal::from_xdr_base64(xdr.trim()).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> Vec<u8> {
+        (0..32).collect()
+    }
+
+    fn round_trip(kind: Kind, payload: &[u8], expected: &str) {
+        let encoded = from_payload(kind, payload).unwrap().to_string();
+        assert_eq!(encoded, expected);
+        assert_eq!(encoded.chars().next(), Some(kind.prefix()));
+
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(Kind::of(&decoded), kind);
+        assert_eq!(super::payload(&decoded), payload);
+        assert_eq!(
+            from_hex(kind, &to_hex(&encoded).unwrap()).as_deref(),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn account() {
+        round_trip(
+            Kind::Account,
+            &key(),
+            "GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZX",
+        );
+    }
+
+    #[test]
+    fn contract() {
+        round_trip(
+            Kind::Contract,
+            &key(),
+            "CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O",
+        );
+    }
+
+    #[test]
+    fn muxed_account() {
+        let payload = [&key()[..], &1234u64.to_be_bytes()[..]].concat();
+        round_trip(
+            Kind::Muxed,
+            &payload,
+            "MAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6AAAAAAAAAAE2KZ3Q",
+        );
+    }
+
+    #[test]
+    fn pre_auth_tx() {
+        round_trip(
+            Kind::PreAuthTx,
+            &key(),
+            "TAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6ULG",
+        );
+    }
+
+    #[test]
+    fn hash_x() {
+        round_trip(
+            Kind::HashX,
+            &key(),
+            "XAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7QO7",
+        );
+    }
+
+    #[test]
+    fn signed_payload() {
+        let payload = [&key()[..], &[1, 2, 3, 4, 5][..]].concat();
+        round_trip(
+            Kind::SignedPayload,
+            &payload,
+            "PAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6AAAAACQCAQDAQCQAAAAMFPQ",
+        );
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        assert!(decode("GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZY").is_none());
+        assert_eq!(
+            kind("GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZX"),
+            Some(Kind::Account)
+        );
+    }
+}
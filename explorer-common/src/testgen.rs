@@ -0,0 +1,121 @@
+use stellar_xdr::{ContractEventBody, ContractEventType, ScObject, ScVal, WriteXdr};
+
+use crate::{strkey, types::common::Invocation};
+
+fn xdr(val: &ScVal) -> String {
+    val.to_xdr_base64().unwrap()
+}
+
+/// A soroban-sdk `#[test]` replaying the invocation of transaction `tx` against the contract
+/// wasm, asserting on the result and the events observed on chain.
+///
+/// Values are embedded as base64 XDR so the test doesn't depend on the contract's types.
+/// The pinned XDR has no authorization entries, the test doesn't set up any auth.
+pub fn test_skeleton(tx: &str, invocation: &Invocation) -> String {
+    let id = strkey::to_hex(&invocation.id).unwrap_or_default();
+    let id_bytes = hex::decode(&id)
+        .unwrap_or_default()
+        .iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut out = format!(
+        r#"#![cfg(test)]
+
+use soroban_sdk::{{
+    testutils::Events as _,
+    xdr::{{ReadXdr, ScVal}},
+    BytesN, Env, RawVal, Symbol, TryFromVal, Vec,
+}};
+
+// the wasm of contract {contract}, e.g. downloaded from the explorer.
+const WASM: &[u8] = include_bytes!("contract.wasm");
+
+fn val(env: &Env, xdr: &str) -> RawVal {{
+    RawVal::try_from_val(env, &ScVal::from_xdr_base64(xdr).unwrap()).unwrap()
+}}
+
+fn xdr(env: &Env, val: RawVal) -> ScVal {{
+    ScVal::try_from_val(env, &val).unwrap()
+}}
+
+// replays {function} from transaction {tx}.
+#[test]{should_panic}
+fn replay_{name}() {{
+    let env = Env::default();
+    // registered under the same id, so that addresses in args and events match.
+    let contract_id = env.register_contract_wasm(&BytesN::from_array(&env, &[{id_bytes}]), WASM);
+
+    let mut args: Vec<RawVal> = Vec::new(&env);
+"#,
+        contract = invocation.id,
+        function = invocation.function,
+        name = tx.chars().take(8).collect::<String>(),
+        // failed invocations have no result and no events to assert on.
+        should_panic = if invocation.result.is_none() {
+            "\n#[should_panic]"
+        } else {
+            ""
+        },
+    );
+
+    for arg in &invocation.args {
+        match arg {
+            Some(arg) => out += &format!("    args.push_back(val(&env, \"{}\"));\n", xdr(arg)),
+            None => out += "    // Horizon returned an argument that couldn't be decoded.\n",
+        }
+    }
+
+    out += &format!(
+        "\n    let {}: RawVal =\n        env.invoke_contract(&contract_id, &Symbol::from_str(\"{}\"), args);\n",
+        if invocation.result.is_some() { "result" } else { "_result" },
+        invocation.function
+    );
+    let result = match &invocation.result {
+        Some(result) => result,
+        None => {
+            let error = invocation.error.as_deref().unwrap_or("unknown error");
+            out += &format!("    // failed on chain: {error}.\n}}\n");
+            return out;
+        }
+    };
+    out += &format!(
+        "    assert_eq!(\n        xdr(&env, result),\n        ScVal::from_xdr_base64(\"{}\").unwrap()\n    );\n",
+        xdr(result)
+    );
+
+    // only contract events are recorded by the test env, events of other contracts need those
+    // contracts to be registered too.
+    let events = invocation
+        .events
+        .iter()
+        .flatten()
+        .filter(|event| event.type_ == ContractEventType::Contract)
+        .map(|event| {
+            let ContractEventBody::V0(body) = &event.body;
+            let topics = ScVal::Object(Some(ScObject::Vec(body.topics.clone())));
+            (xdr(&topics), xdr(&body.data))
+        })
+        .collect::<Vec<_>>();
+
+    out += &format!(
+        "\n    let events = env.events().all();\n    assert_eq!(events.len(), {});\n",
+        events.len()
+    );
+    for (i, (topics, data)) in events.iter().enumerate() {
+        out += &format!(
+            r#"
+    let (_, topics, data) = events.get_unchecked({i}).unwrap();
+    assert_eq!(
+        xdr(&env, topics.to_raw()),
+        ScVal::from_xdr_base64("{topics}").unwrap()
+    );
+    assert_eq!(xdr(&env, data), ScVal::from_xdr_base64("{data}").unwrap());
+"#
+        );
+    }
+
+    out += "}\n";
+    out
+}
@@ -1,10 +1,21 @@
 use stellar_xdr::{
-    HostFunction, InvokeHostFunctionResult, OperationBody, OperationResult, OperationResultTr,
-    ReadXdr, ScObject, ScVal, TransactionEnvelope, TransactionMeta, TransactionMetaV3,
-    TransactionResult, TransactionResultResult, TransactionV1Envelope,
+    FeeBumpTransactionEnvelope, FeeBumpTransactionInnerTx, HostFunction, InnerTransactionResult,
+    InnerTransactionResultPair, InnerTransactionResultResult, InvokeHostFunctionResult,
+    LedgerBounds, Operation, OperationBody, OperationResult, OperationResultTr, PreconditionsV2,
+    ReadXdr, ScObject, ScVal, TimeBounds, Transaction, TransactionEnvelope, TransactionMeta,
+    TransactionMetaV3, TransactionResult, TransactionResultResult, TransactionV0Envelope,
+    TransactionV1Envelope,
 };
 
-use crate::types::common::{Deployed, Event, Invocation};
+use crate::{
+    classic::process_classic_op,
+    contract_id,
+    paging::{parse_time, Toid},
+    signatures::v0_to_v1,
+    storage::storage_changes,
+    strkey,
+    types::common::{Deployed, Event, FeeBump, Invocation, Memo, Preconditions, Processed},
+};
 
 use super::types;
 
@@ -27,147 +38,314 @@ pub async fn get_transaction(base_url: &str, hash: &str) -> Option<types::transa
     }
 }
 
-pub fn process_tx(tx_high: types::transaction::Response) -> super::types::common::Processed {
-    let envelope = stellar_xdr::TransactionEnvelope::from_xdr_base64(tx_high.envelope_xdr);
-    let body = match envelope {
-        Ok(content) => match content {
-            TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => {
-                match tx.operations.get(0) {
-                    Some(op) => match &op.body {
-                        OperationBody::InvokeHostFunction(inv_h_fn_op) => {
-                            match inv_h_fn_op.function {
-                                HostFunction::CreateContractWithSourceAccount => {
-                                    let id = if let Ok(TransactionResult {
-                                        result: TransactionResultResult::TxSuccess(op_results),
-                                        ..
-                                    }) =
-                                        TransactionResult::from_xdr_base64(tx_high.result_xdr)
-                                    {
-                                        if let Some(OperationResult::OpInner(
-                                            OperationResultTr::InvokeHostFunction(
-                                                InvokeHostFunctionResult::Success(ScVal::Object(
-                                                    Some(ScObject::Bytes(id)),
-                                                )),
-                                            ),
-                                        )) = op_results.get(0)
-                                        {
-                                            Some(hex::encode(id))
-                                        } else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    };
-                                    let bytes = if let Some(code) = inv_h_fn_op.parameters.get(0) {
-                                        if let ScVal::Object(Some(ScObject::Bytes(bytes))) = code {
-                                            Some(bytes.into())
-                                        } else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    };
-
-                                    if let (Some(id), Some(bytes)) = (id, bytes) {
-                                        Some(Event::Deployment(Deployed { id, bytes }))
-                                    } else {
-                                        None
-                                    }
-                                }
-                                // HostFunction::CreateContractWithEd25519 => {}
-                                HostFunction::InvokeContract => {
-                                    let ctr_id = if let Some(id) = inv_h_fn_op.parameters.get(0) {
-                                        if let ScVal::Object(Some(ScObject::Bytes(id))) = id {
-                                            Some(hex::encode(id))
-                                        } else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    };
-
-                                    let function =
-                                        if let Some(function) = inv_h_fn_op.parameters.get(1) {
-                                            if let ScVal::Symbol(function) = function {
-                                                Some(function.to_string_lossy())
-                                            } else {
-                                                None
-                                            }
-                                        } else {
-                                            None
-                                        };
-                                    let args = inv_h_fn_op
-                                        .parameters
-                                        .iter()
-                                        .skip(2)
-                                        .map(|a| Some(a.clone()))
-                                        .collect::<Vec<_>>();
-
-                                    let result = if let Ok(TransactionResult {
-                                        result: TransactionResultResult::TxSuccess(op_results),
-                                        ..
-                                    }) =
-                                        TransactionResult::from_xdr_base64(tx_high.result_xdr)
-                                    {
-                                        if let Some(OperationResult::OpInner(
-                                            OperationResultTr::InvokeHostFunction(
-                                                InvokeHostFunctionResult::Success(result),
-                                            ),
-                                        )) = op_results.get(0)
-                                        {
-                                            Some(result.clone())
-                                        } else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    };
-
-                                    let contract_events =
-                                        if let Ok(TransactionMeta::V3(TransactionMetaV3 {
-                                            events,
-                                            ..
-                                        })) = TransactionMeta::from_xdr_base64(
-                                            tx_high.result_meta_xdr,
-                                        ) {
-                                            Some(events.into())
-                                        } else {
-                                            None
-                                        };
-
-                                    let footprint = Some(inv_h_fn_op.footprint.clone());
-
-                                    if let (Some(id), Some(function)) = (ctr_id, function) {
-                                        Some(Event::Invocation(Invocation {
-                                            id,
-                                            function,
-                                            args,
-                                            result,
-                                            footprint,
-                                            events: contract_events,
-                                        }))
-                                    } else {
-                                        None
-                                    }
-                                }
-                                _ => None, // TODO: token contracts
-                            }
+/// Returns the transaction that carries the operations, unwrapping fee bumps.
+pub fn inner_transaction(envelope: TransactionEnvelope) -> (Transaction, Option<FeeBump>) {
+    match envelope {
+        TransactionEnvelope::TxV0(TransactionV0Envelope { tx, .. }) => (v0_to_v1(&tx), None),
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => (tx, None),
+        TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, .. }) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = tx.inner_tx;
+            (
+                inner.tx,
+                Some(FeeBump {
+                    fee_source: strkey::muxed_account(&tx.fee_source),
+                    fee: tx.fee,
+                }),
+            )
+        }
+    }
+}
+
+pub fn fee_bump(envelope_xdr: &str) -> Option<FeeBump> {
+    let envelope = TransactionEnvelope::from_xdr_base64(envelope_xdr).ok()?;
+    inner_transaction(envelope).1
+}
+
+fn decode_memo(memo: &stellar_xdr::Memo) -> Option<Memo> {
+    match memo {
+        stellar_xdr::Memo::None => None,
+        stellar_xdr::Memo::Text(text) => Some(Memo::Text(text.to_string_lossy())),
+        stellar_xdr::Memo::Id(id) => Some(Memo::Id(*id)),
+        stellar_xdr::Memo::Hash(hash) => Some(Memo::Hash(hex::encode(hash.0))),
+        stellar_xdr::Memo::Return(hash) => Some(Memo::Return(hex::encode(hash.0))),
+    }
+}
+
+fn time_bounds(preconditions: &mut Preconditions, time_bounds: &TimeBounds) {
+    // zero means the bound isn't set.
+    preconditions.min_time = Some(time_bounds.min_time.0).filter(|t| *t != 0);
+    preconditions.max_time = Some(time_bounds.max_time.0).filter(|t| *t != 0);
+}
+
+fn decode_preconditions(cond: &stellar_xdr::Preconditions) -> Option<Preconditions> {
+    let mut preconditions = Preconditions::default();
+    match cond {
+        stellar_xdr::Preconditions::None => return None,
+        stellar_xdr::Preconditions::Time(bounds) => time_bounds(&mut preconditions, bounds),
+        stellar_xdr::Preconditions::V2(PreconditionsV2 {
+            time_bounds: bounds,
+            ledger_bounds,
+            min_seq_num,
+            min_seq_age,
+            min_seq_ledger_gap,
+            extra_signers,
+        }) => {
+            if let Some(bounds) = bounds {
+                time_bounds(&mut preconditions, bounds);
+            }
+            if let Some(LedgerBounds {
+                min_ledger,
+                max_ledger,
+            }) = ledger_bounds
+            {
+                preconditions.min_ledger = Some(*min_ledger).filter(|l| *l != 0);
+                preconditions.max_ledger = Some(*max_ledger).filter(|l| *l != 0);
+            }
+            preconditions.min_sequence = min_seq_num.as_ref().map(|seq| seq.0);
+            preconditions.min_sequence_age = Some(min_seq_age.0).filter(|age| *age != 0);
+            preconditions.min_sequence_ledger_gap =
+                Some(*min_seq_ledger_gap).filter(|gap| *gap != 0);
+            preconditions.extra_signers = extra_signers.iter().map(strkey::signer_key).collect();
+        }
+    }
+    Some(preconditions)
+}
+
+pub fn memo(envelope_xdr: &str) -> Option<Memo> {
+    let envelope = TransactionEnvelope::from_xdr_base64(envelope_xdr).ok()?;
+    decode_memo(&inner_transaction(envelope).0.memo)
+}
+
+pub fn preconditions(envelope_xdr: &str) -> Option<Preconditions> {
+    let envelope = TransactionEnvelope::from_xdr_base64(envelope_xdr).ok()?;
+    decode_preconditions(&inner_transaction(envelope).0.cond)
+}
+
+/// Results of the operations of a transaction, fee bumped or not. Failed transactions only
+/// have them when an operation failed.
+pub fn operation_results(result_xdr: &str) -> Option<Vec<OperationResult>> {
+    match TransactionResult::from_xdr_base64(result_xdr).ok()?.result {
+        TransactionResultResult::TxSuccess(op_results)
+        | TransactionResultResult::TxFailed(op_results) => Some(op_results.to_vec()),
+        TransactionResultResult::TxFeeBumpInnerSuccess(InnerTransactionResultPair {
+            result: InnerTransactionResult { result, .. },
+            ..
+        })
+        | TransactionResultResult::TxFeeBumpInnerFailed(InnerTransactionResultPair {
+            result: InnerTransactionResult { result, .. },
+            ..
+        }) => match result {
+            InnerTransactionResultResult::TxSuccess(op_results)
+            | InnerTransactionResultResult::TxFailed(op_results) => Some(op_results.to_vec()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Why a host function invocation failed, `None` when it succeeded.
+pub fn invocation_error(op_result: Option<&OperationResult>) -> Option<String> {
+    match op_result? {
+        OperationResult::OpInner(OperationResultTr::InvokeHostFunction(
+            InvokeHostFunctionResult::Success(_),
+        )) => None,
+        OperationResult::OpInner(OperationResultTr::InvokeHostFunction(result)) => {
+            Some(format!("{result:?}"))
+        }
+        other => Some(format!("{other:?}")),
+    }
+}
+
+fn process_op(
+    tx: &Transaction,
+    op: &Operation,
+    op_result: Option<&OperationResult>,
+    result_meta_xdr: &str,
+    network_passphrase: Option<&str>,
+) -> Option<Event> {
+    match &op.body {
+        OperationBody::InvokeHostFunction(inv_h_fn_op) => {
+            match inv_h_fn_op.function {
+                HostFunction::CreateContractWithSourceAccount => {
+                    let id = if let Some(OperationResult::OpInner(
+                        OperationResultTr::InvokeHostFunction(InvokeHostFunctionResult::Success(
+                            ScVal::Object(Some(ScObject::Bytes(id))),
+                        )),
+                    )) = op_result
+                    {
+                        strkey::contract_from_bytes(id)
+                    } else {
+                        None
+                    };
+                    let bytes = if let Some(code) = inv_h_fn_op.parameters.get(0) {
+                        if let ScVal::Object(Some(ScObject::Bytes(bytes))) = code {
+                            Some(bytes.into())
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    };
+
+                    let salt = if let Some(ScVal::Object(Some(ScObject::Bytes(salt)))) =
+                        inv_h_fn_op.parameters.get(1)
+                    {
+                        salt.as_slice().try_into().ok()
+                    } else {
+                        None
+                    };
+                    let source_account = strkey::muxed_to_account_id(
+                        op.source_account.as_ref().unwrap_or(&tx.source_account),
+                    );
+                    let derived_id = match (network_passphrase, salt) {
+                        (Some(passphrase), Some(salt)) => {
+                            contract_id::from_source_account(passphrase, &source_account, salt)
                         }
                         _ => None,
-                    },
+                    };
+
+                    if let (Some(id), Some(bytes)) = (id, bytes) {
+                        Some(Event::Deployment(Deployed {
+                            id,
+                            bytes,
+                            derived_id,
+                        }))
+                    } else {
+                        None
+                    }
+                }
+                // HostFunction::CreateContractWithEd25519 => {}
+                HostFunction::InvokeContract => {
+                    let ctr_id = if let Some(id) = inv_h_fn_op.parameters.get(0) {
+                        if let ScVal::Object(Some(ScObject::Bytes(id))) = id {
+                            strkey::contract_from_bytes(id)
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    };
+
+                    let function = if let Some(function) = inv_h_fn_op.parameters.get(1) {
+                        if let ScVal::Symbol(function) = function {
+                            Some(function.to_string_lossy())
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    };
+                    let args = inv_h_fn_op
+                        .parameters
+                        .iter()
+                        .skip(2)
+                        .map(|a| Some(a.clone()))
+                        .collect::<Vec<_>>();
+
+                    let result = if let Some(OperationResult::OpInner(
+                        OperationResultTr::InvokeHostFunction(InvokeHostFunctionResult::Success(
+                            result,
+                        )),
+                    )) = op_result
+                    {
+                        Some(result.clone())
+                    } else {
+                        None
+                    };
+
+                    let contract_events =
+                        if let Ok(TransactionMeta::V3(TransactionMetaV3 { events, .. })) =
+                            TransactionMeta::from_xdr_base64(result_meta_xdr)
+                        {
+                            Some(events.into())
+                        } else {
+                            None
+                        };
 
-                    None => None,
+                    let error = invocation_error(op_result);
+                    let footprint = Some(inv_h_fn_op.footprint.clone());
+
+                    if let (Some(id), Some(function)) = (ctr_id, function) {
+                        Some(Event::Invocation(Invocation {
+                            id,
+                            function,
+                            args,
+                            result,
+                            error,
+                            footprint,
+                            events: contract_events,
+                        }))
+                    } else {
+                        None
+                    }
                 }
+                _ => None, // TODO: token contracts
             }
-            _ => None,
-        },
-        Err(_) => None,
+        }
+        body => Some(process_classic_op(body)),
+    }
+}
+
+/// Decodes every operation of the transaction, in order. Deployments only get a
+/// `derived_id` when the network passphrase is known.
+pub fn process_tx_operations(
+    tx_high: types::transaction::Response,
+    network_passphrase: Option<&str>,
+) -> Vec<Processed> {
+    let envelope = match TransactionEnvelope::from_xdr_base64(&tx_high.envelope_xdr) {
+        Ok(envelope) => envelope,
+        Err(_) => return vec![],
+    };
+    let (tx, fee_bump) = inner_transaction(envelope);
+    let (toid, at) = match (
+        Toid::parse(&tx_high.paging_token),
+        parse_time(&tx_high.created_at),
+    ) {
+        (Some(toid), Some(at)) => (toid, at),
+        _ => return vec![],
     };
+    let op_results = operation_results(&tx_high.result_xdr);
+    let memo = decode_memo(&tx.memo);
+    let preconditions = decode_preconditions(&tx.cond);
 
-    super::types::common::Processed {
-        source_account: tx_high.source_account,
-        tx: tx_high.id,
-        at: tx_high.created_at,
-        body: body.unwrap(), // should be safe
-    }
+    tx.operations
+        .iter()
+        .enumerate()
+        .filter_map(|(i, op)| {
+            let body = process_op(
+                &tx,
+                op,
+                op_results.as_ref().and_then(|results| results.get(i)),
+                &tx_high.result_meta_xdr,
+                network_passphrase,
+            )?;
+
+            Some(Processed {
+                source_account: op
+                    .source_account
+                    .as_ref()
+                    .map(strkey::muxed_account)
+                    .unwrap_or_else(|| tx_high.source_account.clone()),
+                tx: tx_high.id.clone(),
+                at,
+                ledger: toid.ledger,
+                application_order: toid.application_order,
+                paging_token: toid.operation(i as u32 + 1).paging_token(),
+                fee_bump: fee_bump.clone(),
+                memo: memo.clone(),
+                preconditions: preconditions.clone(),
+                storage_changes: storage_changes(&tx_high.result_meta_xdr, i),
+                body,
+            })
+        })
+        .collect()
+}
+
+pub fn process_tx(
+    tx_high: types::transaction::Response,
+    network_passphrase: Option<&str>,
+) -> Option<Processed> {
+    process_tx_operations(tx_high, network_passphrase)
+        .into_iter()
+        .next()
 }
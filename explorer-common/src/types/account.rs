@@ -0,0 +1,27 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub id: String,
+    pub account_id: String,
+    pub sequence: String,
+    pub subentry_count: i64,
+    pub last_modified_ledger: i64,
+    pub thresholds: Thresholds,
+    pub signers: Vec<Signer>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Thresholds {
+    pub low_threshold: u32,
+    pub med_threshold: u32,
+    pub high_threshold: u32,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Signer {
+    pub weight: u32,
+    pub key: String,
+    #[serde(rename = "type")]
+    pub type_field: String,
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use stellar_xdr::{ContractEvent, LedgerFootprint, ScVal};
 
@@ -7,6 +8,9 @@ pub struct Invocation {
     pub function: String,
     pub args: Vec<Option<ScVal>>,
     pub result: Option<ScVal>,
+    // set when the invocation failed, the host function result or the operation result code.
+    #[serde(default)]
+    pub error: Option<String>,
     pub footprint: Option<LedgerFootprint>,
     pub events: Option<Vec<ContractEvent>>,
 }
@@ -14,19 +18,148 @@ pub struct Invocation {
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Deployed {
     pub id: String,
+    #[serde(with = "hex")]
     pub bytes: Vec<u8>,
+    // id derived from the deployer and salt, should always match `id`.
+    pub derived_id: Option<String>,
+}
+
+// amounts are decimal strings with 7 digits, accounts and assets are strkeys.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CreateAccount {
+    pub destination: String,
+    pub starting_balance: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Payment {
+    pub destination: String,
+    pub asset: String,
+    pub amount: String,
+}
+
+// send amount is the maximum for strict receive, dest amount the minimum for strict send.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PathPayment {
+    pub destination: String,
+    pub send_asset: String,
+    pub send_amount: String,
+    pub dest_asset: String,
+    pub dest_amount: String,
+    pub strict_send: bool,
+}
+
+// a zero limit removes the trustline.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ChangeTrust {
+    pub asset: String,
+    pub limit: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TrustlineFlags {
+    pub trustor: String,
+    pub asset: String,
+    pub set_flags: u32,
+    pub clear_flags: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AccountMerge {
+    pub destination: String,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub enum Event {
     Deployment(Deployed),
     Invocation(Invocation),
+    CreateAccount(CreateAccount),
+    Payment(Payment),
+    PathPayment(PathPayment),
+    ChangeTrust(ChangeTrust),
+    TrustlineFlags(TrustlineFlags),
+    AccountMerge(AccountMerge),
+    // operations that aren't decoded yet, by name.
+    Other(String),
+}
+
+impl Event {
+    /// Short description of the event, the function name for invocations.
+    pub fn action(&self) -> String {
+        match self {
+            Event::Deployment(_) => String::from("deploy"),
+            Event::Invocation(invocation) => invocation.function.clone(),
+            Event::CreateAccount(_) => String::from("create account"),
+            Event::Payment(_) => String::from("payment"),
+            Event::PathPayment(_) => String::from("path payment"),
+            Event::ChangeTrust(_) => String::from("change trust"),
+            Event::TrustlineFlags(_) => String::from("set trustline flags"),
+            Event::AccountMerge(_) => String::from("account merge"),
+            Event::Other(name) => name.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FeeBump {
+    pub fee_source: String,
+    pub fee: i64,
+}
+
+// hashes are hex encoded.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Memo {
+    Text(String),
+    Id(u64),
+    Hash(String),
+    Return(String),
+}
+
+// times are unix timestamps, bounds that aren't set are `None`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Preconditions {
+    pub min_time: Option<u64>,
+    pub max_time: Option<u64>,
+    pub min_ledger: Option<u32>,
+    pub max_ledger: Option<u32>,
+    pub min_sequence: Option<i64>,
+    pub min_sequence_age: Option<u64>,
+    pub min_sequence_ledger_gap: Option<u32>,
+    pub extra_signers: Vec<String>,
+}
+
+// the pinned XDR has a single kind of contract data, the entry under the
+// `LedgerKeyContractCode` key is the contract instance and everything else is persistent.
+// contract data has no durability yet, so there is no temporary storage to tell apart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Storage {
+    Instance,
+    Persistent,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StorageChange {
+    pub contract_id: String,
+    pub storage: Storage,
+    pub key: ScVal,
+    // `None` when the entry was removed.
+    pub val: Option<ScVal>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Processed {
     pub source_account: String,
     pub tx: String,
-    pub at: String,
+    pub at: DateTime<Utc>,
+    pub ledger: u32,
+    // position of the transaction in its ledger.
+    pub application_order: u32,
+    // paging token of the operation.
+    pub paging_token: String,
+    pub fee_bump: Option<FeeBump>,
+    pub memo: Option<Memo>,
+    pub preconditions: Option<Preconditions>,
+    // contract data written by the operation.
+    pub storage_changes: Vec<StorageChange>,
     pub body: Event,
 }
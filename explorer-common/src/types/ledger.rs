@@ -0,0 +1,22 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub id: String,
+    pub paging_token: String,
+    pub hash: String,
+    pub sequence: u32,
+    pub successful_transaction_count: u32,
+    pub failed_transaction_count: u32,
+    pub operation_count: u32,
+    pub closed_at: String,
+    pub protocol_version: u32,
+}
+
+// the parts of Horizon's root resource telling which ledgers it has history for.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Root {
+    pub history_latest_ledger: u32,
+    pub history_elder_ledger: u32,
+    pub network_passphrase: String,
+}
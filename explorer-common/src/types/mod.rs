@@ -1,3 +1,5 @@
+pub mod account;
 pub mod common;
+pub mod ledger;
 pub mod operation;
 pub mod transaction;
@@ -24,24 +24,111 @@ pub struct Embedded {
     pub records: Vec<Record>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Record {
     pub id: String,
     pub paging_token: String,
     pub transaction_successful: bool,
     pub source_account: String,
-    pub r#type: String,
     pub type_i: i64,
     pub created_at: String,
     pub transaction_hash: String,
+    #[serde(flatten)]
+    pub body: Body,
+}
+
+impl Record {
+    pub fn invoke_host_function(&self) -> Option<&InvokeHostFunction> {
+        match &self.body {
+            Body::Known(Operation::InvokeHostFunction(op)) => Some(op),
+            _ => None,
+        }
+    }
+}
+
+// operation types we don't model, or records that don't match their model, are kept as they
+// come from Horizon.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Body {
+    Known(Operation),
+    Unknown(serde_json::Value),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Operation {
+    CreateAccount(CreateAccount),
+    Payment(Payment),
+    PathPaymentStrictReceive(PathPayment),
+    PathPaymentStrictSend(PathPayment),
+    InvokeHostFunction(InvokeHostFunction),
+    ExtendFootprintTtl(ExtendFootprintTtl),
+    RestoreFootprint(RestoreFootprint),
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateAccount {
+    pub starting_balance: String,
+    pub funder: String,
+    pub account: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Payment {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub amount: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathPayment {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub amount: String,
+    pub source_asset_type: String,
+    pub source_asset_code: Option<String>,
+    pub source_asset_issuer: Option<String>,
+    pub source_amount: String,
+    // only on strict receive.
+    pub source_max: Option<String>,
+    // only on strict send.
+    pub destination_min: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvokeHostFunction {
+    pub function: HostFunction,
     #[serde(default)]
     pub parameters: Vec<Parameter>,
-    pub function: Option<String>,
     pub footprint: Option<String>,
-    pub funder: Option<String>,
-    pub account: Option<String>,
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HostFunction {
+    #[serde(rename = "HostFunctionHostFnInvokeContract")]
+    InvokeContract,
+    #[serde(rename = "HostFunctionHostFnCreateContractWithSourceAccount")]
+    CreateContractWithSourceAccount,
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtendFootprintTtl {
+    pub extend_to: u32,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestoreFootprint {}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
     pub value: String,
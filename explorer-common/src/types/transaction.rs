@@ -1,7 +1,26 @@
 use serde_derive::{Deserialize, Serialize};
 
+use super::operation::Links as PageLinks;
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page {
+    #[serde(rename = "_links")]
+    pub links: PageLinks,
+    #[serde(rename = "_embedded")]
+    pub embedded: Embedded,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Embedded {
+    pub records: Vec<Response>,
+}
+
+// fields that Horizon only sets for some transactions default to empty, unknown fields are
+// ignored.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Response {
+    #[serde(rename = "_links", default)]
+    pub links: Links,
     pub id: String,
     pub paging_token: String,
     pub successful: bool,
@@ -9,15 +28,96 @@ pub struct Response {
     pub ledger: i64,
     pub created_at: String,
     pub source_account: String,
+    #[serde(default)]
+    pub account_muxed: Option<String>,
+    #[serde(default)]
+    pub account_muxed_id: Option<String>,
     pub source_account_sequence: String,
     pub fee_account: String,
+    #[serde(default)]
+    pub fee_account_muxed: Option<String>,
+    #[serde(default)]
+    pub fee_account_muxed_id: Option<String>,
     pub fee_charged: String,
     pub max_fee: String,
     pub operation_count: i64,
     pub envelope_xdr: String,
     pub result_xdr: String,
     pub result_meta_xdr: String,
+    #[serde(default)]
     pub fee_meta_xdr: String,
     pub memo_type: String,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(default)]
+    pub memo_bytes: Option<String>,
+    #[serde(default)]
     pub signatures: Vec<String>,
+    #[serde(default)]
+    pub valid_after: Option<String>,
+    #[serde(default)]
+    pub valid_before: Option<String>,
+    #[serde(default)]
+    pub preconditions: Option<Preconditions>,
+    #[serde(default)]
+    pub fee_bump_transaction: Option<FeeBumpTransaction>,
+    #[serde(default)]
+    pub inner_transaction: Option<InnerTransaction>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Links {
+    #[serde(rename = "self")]
+    pub self_field: Option<Link>,
+    pub account: Option<Link>,
+    pub ledger: Option<Link>,
+    pub operations: Option<Link>,
+    pub effects: Option<Link>,
+    pub precedes: Option<Link>,
+    pub succeeds: Option<Link>,
+    pub transaction: Option<Link>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Link {
+    pub href: String,
+    #[serde(default)]
+    pub templated: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preconditions {
+    pub timebounds: Option<TimeBounds>,
+    pub ledgerbounds: Option<LedgerBounds>,
+    pub min_account_sequence: Option<String>,
+    pub min_account_sequence_age: Option<String>,
+    pub min_account_sequence_ledger_gap: Option<u32>,
+    #[serde(default)]
+    pub extra_signers: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeBounds {
+    pub min_time: Option<String>,
+    pub max_time: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerBounds {
+    #[serde(default)]
+    pub min_ledger: u32,
+    pub max_ledger: Option<u32>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeBumpTransaction {
+    pub hash: String,
+    pub signatures: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InnerTransaction {
+    pub hash: String,
+    pub signatures: Vec<String>,
+    pub max_fee: String,
 }
@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+
+use serde_derive::{Deserialize, Serialize};
+use stellar_xdr::{
+    ScSpecEntry, ScSpecFunctionV0, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionV0,
+};
+
+use crate::{
+    types::common::Deployed,
+    wasm::{self, WasmInfo},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Difference {
+    // what differs, e.g. `export transfer` or `spec fn transfer`.
+    pub item: String,
+    // `None` when the item is missing from that side.
+    pub local: Option<String>,
+    pub deployed: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Verification {
+    pub contract_id: String,
+    pub local_hash: String,
+    pub deployed_hash: String,
+    pub matches: bool,
+    // empty when the hashes match.
+    pub differences: Vec<Difference>,
+}
+
+fn spec_name(entry: &ScSpecEntry) -> String {
+    match entry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 { name, .. }) => {
+            format!("fn {}", name.to_string_lossy())
+        }
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 { name, .. }) => {
+            format!("struct {}", name.to_string_lossy())
+        }
+        ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 { name, .. }) => {
+            format!("union {}", name.to_string_lossy())
+        }
+        ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 { name, .. }) => {
+            format!("enum {}", name.to_string_lossy())
+        }
+        ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 { name, .. }) => {
+            format!("error {}", name.to_string_lossy())
+        }
+    }
+}
+
+// compares two sets of items by key, reporting the ones missing from a side or with a
+// different value.
+fn diff(
+    differences: &mut Vec<Difference>,
+    prefix: &str,
+    local: BTreeMap<String, String>,
+    mut deployed: BTreeMap<String, String>,
+) {
+    for (key, local) in local {
+        match deployed.remove(&key) {
+            Some(deployed) if deployed == local => {}
+            deployed => differences.push(Difference {
+                item: format!("{prefix} {key}"),
+                local: Some(local),
+                deployed,
+            }),
+        }
+    }
+    for (key, deployed) in deployed {
+        differences.push(Difference {
+            item: format!("{prefix} {key}"),
+            local: None,
+            deployed: Some(deployed),
+        });
+    }
+}
+
+fn differences(
+    local: &[u8],
+    local_info: WasmInfo,
+    deployed: &[u8],
+    deployed_info: WasmInfo,
+) -> Vec<Difference> {
+    let mut differences = vec![];
+
+    let exports = |info: &WasmInfo| {
+        info.exports
+            .iter()
+            .map(|export| (export.clone(), String::from("exported")))
+            .collect()
+    };
+    diff(
+        &mut differences,
+        "export",
+        exports(&local_info),
+        exports(&deployed_info),
+    );
+
+    let spec = |info: &WasmInfo| {
+        info.spec
+            .iter()
+            .map(|entry| (spec_name(entry), serde_json::to_string(entry).unwrap()))
+            .collect()
+    };
+    diff(
+        &mut differences,
+        "spec",
+        spec(&local_info),
+        spec(&deployed_info),
+    );
+
+    let sections = |info: &WasmInfo| {
+        info.custom_sections
+            .iter()
+            .map(|section| {
+                (
+                    section.name.clone(),
+                    format!("{} bytes, {}", section.size, section.hash),
+                )
+            })
+            .collect()
+    };
+    diff(
+        &mut differences,
+        "section",
+        sections(&local_info),
+        sections(&deployed_info),
+    );
+
+    if local_info.interface_version != deployed_info.interface_version {
+        differences.push(Difference {
+            item: String::from("env interface version"),
+            local: local_info.interface_version.map(|v| v.to_string()),
+            deployed: deployed_info.interface_version.map(|v| v.to_string()),
+        });
+    }
+    if local_info.meta != deployed_info.meta {
+        differences.push(Difference {
+            item: String::from(wasm::META_SECTION),
+            local: local_info.meta,
+            deployed: deployed_info.meta,
+        });
+    }
+
+    // same interface but different hashes, tell which functions were compiled differently.
+    let bodies = |wasm: &[u8]| {
+        wasm::functions(wasm)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|function| {
+                let name = function
+                    .name
+                    .unwrap_or_else(|| format!("${}", function.index));
+                (name, format!("{} bytes, {}", function.size, function.hash))
+            })
+            .collect()
+    };
+    diff(&mut differences, "func", bodies(local), bodies(deployed));
+
+    differences
+}
+
+/// Compares a locally built module with the code of a deployment, `None` when either
+/// isn't valid wasm.
+pub fn verify(local: &[u8], deployed: &Deployed) -> Option<Verification> {
+    let local_info = wasm::inspect(local)?;
+    let deployed_info = wasm::inspect(&deployed.bytes)?;
+    let matches = local_info.hash == deployed_info.hash;
+
+    Some(Verification {
+        contract_id: deployed.id.clone(),
+        local_hash: local_info.hash.clone(),
+        deployed_hash: deployed_info.hash.clone(),
+        matches,
+        differences: if matches {
+            vec![]
+        } else {
+            differences(local, local_info, &deployed.bytes, deployed_info)
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = "CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O";
+
+    // a module exporting `name`, a function returning `result`.
+    fn module(name: &str, result: u8) -> Vec<u8> {
+        assert!(result < 64, "single byte LEB128");
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        // () -> i32
+        wasm.extend([0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f]);
+        wasm.extend([0x03, 0x02, 0x01, 0x00]);
+        wasm.extend([0x07, 4 + name.len() as u8, 0x01, name.len() as u8]);
+        wasm.extend(name.as_bytes());
+        wasm.extend([0x00, 0x00]);
+        // i32.const result
+        wasm.extend([0x0a, 0x06, 0x01, 0x04, 0x00, 0x41, result, 0x0b]);
+        wasm
+    }
+
+    fn deployed(bytes: Vec<u8>) -> Deployed {
+        Deployed {
+            id: String::from(CONTRACT),
+            bytes,
+            derived_id: None,
+        }
+    }
+
+    #[test]
+    fn matching() {
+        let verification = verify(&module("hello", 1), &deployed(module("hello", 1))).unwrap();
+        assert!(verification.matches);
+        assert_eq!(verification.contract_id, CONTRACT);
+        assert_eq!(verification.local_hash, verification.deployed_hash);
+        assert_eq!(
+            verification.local_hash,
+            hex::encode(wasm::hash(&module("hello", 1)))
+        );
+        assert!(verification.differences.is_empty());
+    }
+
+    #[test]
+    fn not_matching() {
+        let verification = verify(&module("hello", 1), &deployed(module("world", 1))).unwrap();
+        assert!(!verification.matches);
+        assert_ne!(verification.local_hash, verification.deployed_hash);
+        let items = verification
+            .differences
+            .iter()
+            .map(|difference| difference.item.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            ["export hello", "export world", "func hello", "func world"]
+        );
+        assert_eq!(
+            verification.differences[0].local.as_deref(),
+            Some("exported")
+        );
+        assert_eq!(verification.differences[0].deployed, None);
+
+        // same interface and body sizes, only the code differs.
+        let verification = verify(&module("hello", 1), &deployed(module("hello", 2))).unwrap();
+        assert!(!verification.matches);
+        assert_ne!(verification.local_hash, verification.deployed_hash);
+        assert_eq!(verification.differences.len(), 1);
+        let difference = &verification.differences[0];
+        assert_eq!(difference.item, "func hello");
+        // no locals, i32.const 1, end.
+        let body = hex::encode(wasm::hash(&[0x00, 0x41, 1, 0x0b]));
+        assert_eq!(difference.local, Some(format!("4 bytes, {body}")));
+        assert_ne!(difference.local, difference.deployed);
+    }
+
+    #[test]
+    fn invalid_wasm() {
+        assert!(verify(b"not wasm", &deployed(module("hello", 1))).is_none());
+    }
+}
@@ -0,0 +1,206 @@
+use std::io::Cursor;
+
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use stellar_xdr::{ReadXdr, ScEnvMetaEntry, ScSpecEntry};
+use wasmparser::{ExternalKind, Parser, Payload, TypeRef};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Import {
+    pub module: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CustomSection {
+    pub name: String,
+    pub size: usize,
+    // hex-encoded SHA-256 of the section's contents.
+    pub hash: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Function {
+    // index in the function space, imports come first.
+    pub index: u32,
+    // export name, if exported.
+    pub name: Option<String>,
+    // size of the body in bytes.
+    pub size: usize,
+    // hex-encoded SHA-256 of the body.
+    pub hash: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WasmInfo {
+    // hex-encoded SHA-256 of the module, the hash contract code is stored under.
+    pub hash: String,
+    pub size: usize,
+    pub exports: Vec<String>,
+    // host functions, the env exposes them as short module and function names.
+    pub imports: Vec<Import>,
+    pub custom_sections: Vec<CustomSection>,
+    // env interface version from `contractenvmetav0`.
+    pub interface_version: Option<u64>,
+    // entries of `contractspecv0`.
+    pub spec: Vec<ScSpecEntry>,
+    // raw `contractmetav0`, the pinned XDR doesn't define its entries.
+    pub meta: Option<String>,
+}
+
+pub const ENV_META_SECTION: &str = "contractenvmetav0";
+pub const META_SECTION: &str = "contractmetav0";
+pub const SPEC_SECTION: &str = "contractspecv0";
+
+pub fn hash(wasm: &[u8]) -> [u8; 32] {
+    Sha256::digest(wasm).into()
+}
+
+// custom sections are a plain concatenation of XDR values.
+fn read_entries<T: ReadXdr>(data: &[u8]) -> Option<Vec<T>> {
+    let mut cursor = Cursor::new(data);
+    let mut entries = vec![];
+    while (cursor.position() as usize) < data.len() {
+        entries.push(T::read_xdr(&mut cursor).ok()?);
+    }
+    Some(entries)
+}
+
+pub fn spec_entries(data: &[u8]) -> Option<Vec<ScSpecEntry>> {
+    read_entries(data)
+}
+
+/// Parses the module, `None` when it isn't valid wasm.
+pub fn inspect(wasm: &[u8]) -> Option<WasmInfo> {
+    let mut info = WasmInfo {
+        hash: hex::encode(hash(wasm)),
+        size: wasm.len(),
+        exports: vec![],
+        imports: vec![],
+        custom_sections: vec![],
+        interface_version: None,
+        spec: vec![],
+        meta: None,
+    };
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload.ok()? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import.ok()?;
+                    if let TypeRef::Func(_) = import.ty {
+                        info.imports.push(Import {
+                            module: import.module.to_string(),
+                            name: import.name.to_string(),
+                        });
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export.ok()?;
+                    if let ExternalKind::Func = export.kind {
+                        info.exports.push(export.name.to_string());
+                    }
+                }
+            }
+            Payload::CustomSection(reader) => {
+                info.custom_sections.push(CustomSection {
+                    name: reader.name().to_string(),
+                    size: reader.data().len(),
+                    hash: hex::encode(hash(reader.data())),
+                });
+
+                match reader.name() {
+                    ENV_META_SECTION => {
+                        for entry in read_entries::<ScEnvMetaEntry>(reader.data())? {
+                            let ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion(version) = entry;
+                            info.interface_version = Some(version);
+                        }
+                    }
+                    SPEC_SECTION => info.spec = spec_entries(reader.data())?,
+                    META_SECTION => info.meta = Some(hex::encode(reader.data())),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(info)
+}
+
+/// Functions defined by the module, in order.
+pub fn functions(wasm: &[u8]) -> Option<Vec<Function>> {
+    let mut imported = 0;
+    let mut exports = vec![];
+    let mut functions = vec![];
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload.ok()? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    if let TypeRef::Func(_) = import.ok()?.ty {
+                        imported += 1;
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export.ok()?;
+                    if let ExternalKind::Func = export.kind {
+                        exports.push((export.index, export.name.to_string()));
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                let index = imported + functions.len() as u32;
+                functions.push(Function {
+                    index,
+                    name: exports
+                        .iter()
+                        .find(|(i, _)| *i == index)
+                        .map(|(_, name)| name.clone()),
+                    size: body.range().len(),
+                    hash: hex::encode(hash(&wasm[body.range()])),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Some(functions)
+}
+
+/// WAT text of the module, `None` when it isn't valid wasm.
+pub fn disassemble(wasm: &[u8]) -> Option<String> {
+    wasmprinter::print_bytes(wasm).ok()
+}
+
+/// Splits the WAT of a module in the text of each defined function, in order, and the rest
+/// of the module.
+pub fn split_functions(wat: &str) -> (Vec<String>, String) {
+    let mut functions: Vec<String> = vec![];
+    let mut rest = String::new();
+    let mut in_function = false;
+
+    for line in wat.lines() {
+        // module fields are indented by two spaces, anything deeper belongs to the field.
+        if line.starts_with("  (") {
+            in_function = line.starts_with("  (func ");
+            if in_function {
+                functions.push(String::new());
+            }
+        } else if !line.starts_with("   ") {
+            in_function = false;
+        }
+        let out = match functions.last_mut() {
+            Some(function) if in_function => function,
+            _ => &mut rest,
+        };
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    (functions, rest)
+}
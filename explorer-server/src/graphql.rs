@@ -0,0 +1,497 @@
+use async_graphql::{
+    http::GraphiQLSource, Context, EmptyMutation, EmptySubscription, InputObject, Json, Object,
+    Schema, SimpleObject, Union,
+};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{response::Html, routing::get, Extension, Router};
+use chrono::{DateTime, Utc};
+use explorer_common::{
+    strkey,
+    types::common::{self, Event, Processed},
+    wasm::{self, WasmInfo},
+};
+use stellar_xdr::{ContractEventBody, LedgerFootprint, ScVal};
+
+use crate::routes::SharedIndex;
+
+const DEFAULT_FIRST: usize = 20;
+const MAX_FIRST: usize = 200;
+
+pub type ExplorerSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn router(index: SharedIndex) -> Router<SharedIndex> {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(index)
+        .finish();
+
+    Router::new()
+        .route("/graphql", get(graphiql).post(graphql_handler))
+        .layer(Extension(schema))
+}
+
+async fn graphql_handler(
+    Extension(schema): Extension<ExplorerSchema>,
+    req: GraphQLRequest,
+) -> GraphQLResponse {
+    schema.execute(req.into_inner()).await.into()
+}
+
+async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+#[derive(InputObject, Default)]
+pub struct Filter {
+    function: Option<String>,
+    source_account: Option<String>,
+    /// Inclusive lower bound on the transaction's close time.
+    from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on the transaction's close time.
+    to: Option<DateTime<Utc>>,
+    /// Inclusive lower bound on the ledger sequence.
+    from_ledger: Option<u32>,
+    /// Exclusive upper bound on the ledger sequence.
+    to_ledger: Option<u32>,
+}
+
+impl Filter {
+    fn matches(&self, processed: &Processed) -> bool {
+        if let Some(function) = &self.function {
+            match &processed.body {
+                Event::Invocation(invocation) if &invocation.function == function => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(source_account) = &self.source_account {
+            if &processed.source_account != source_account {
+                return false;
+            }
+        }
+
+        if let Some(from) = &self.from {
+            if &processed.at < from {
+                return false;
+            }
+        }
+
+        if let Some(to) = &self.to {
+            if &processed.at >= to {
+                return false;
+            }
+        }
+
+        if let Some(from_ledger) = self.from_ledger {
+            if processed.ledger < from_ledger {
+                return false;
+            }
+        }
+
+        if let Some(to_ledger) = self.to_ledger {
+            if processed.ledger >= to_ledger {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn page<T>(
+    items: impl Iterator<Item = T>,
+    first: Option<usize>,
+    skip: Option<usize>,
+) -> impl Iterator<Item = T> {
+    items
+        .skip(skip.unwrap_or(0))
+        .take(first.unwrap_or(DEFAULT_FIRST).min(MAX_FIRST))
+}
+
+/// Filters and pages the borrowed records, cloning only the ones on the page.
+fn select<'a>(
+    records: impl Iterator<Item = &'a Processed>,
+    filter: Option<Filter>,
+    first: Option<usize>,
+    skip: Option<usize>,
+) -> impl Iterator<Item = Processed> {
+    let filter = filter.unwrap_or_default();
+    page(records.filter(move |p| filter.matches(p)), first, skip).cloned()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Accepts the contract id either as a strkey or hex-encoded.
+    async fn contract(&self, id: String) -> Contract {
+        Contract {
+            id: strkey::normalize_contract_id(&id).unwrap_or(id),
+        }
+    }
+
+    async fn account(&self, id: String) -> Account {
+        Account { id }
+    }
+
+    async fn transaction(&self, ctx: &Context<'_>, hash: String) -> Option<Operation> {
+        let index = ctx.data_unchecked::<SharedIndex>().read().await;
+        index.transaction(&hash).and_then(Operation::new)
+    }
+
+    async fn invocations(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Filter>,
+        first: Option<usize>,
+        skip: Option<usize>,
+    ) -> Vec<Invocation> {
+        let index = ctx.data_unchecked::<SharedIndex>().read().await;
+        select(index.invocations(), filter, first, skip)
+            .filter_map(Invocation::new)
+            .collect()
+    }
+
+    async fn deployments(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Filter>,
+        first: Option<usize>,
+        skip: Option<usize>,
+    ) -> Vec<Deployment> {
+        let index = ctx.data_unchecked::<SharedIndex>().read().await;
+        select(index.deployments(), filter, first, skip)
+            .filter_map(Deployment::new)
+            .collect()
+    }
+}
+
+pub struct Contract {
+    id: String,
+}
+
+#[Object]
+impl Contract {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn deployment(&self, ctx: &Context<'_>) -> Option<Deployment> {
+        let index = ctx.data_unchecked::<SharedIndex>().read().await;
+        index.deployment(&self.id).and_then(Deployment::new)
+    }
+
+    async fn invocations(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Filter>,
+        first: Option<usize>,
+        skip: Option<usize>,
+    ) -> Vec<Invocation> {
+        let index = ctx.data_unchecked::<SharedIndex>().read().await;
+        select(index.contract_records(&self.id), filter, first, skip)
+            .filter_map(Invocation::new)
+            .collect()
+    }
+
+    /// Events emitted by this contract, including the ones emitted while being
+    /// called by another contract. `first` and `skip` count events, not invocations.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Filter>,
+        first: Option<usize>,
+        skip: Option<usize>,
+    ) -> Vec<ContractEvent> {
+        let index = ctx.data_unchecked::<SharedIndex>().read().await;
+        let filter = filter.unwrap_or_default();
+        let events = index
+            .event_records(&self.id)
+            .filter(|p| filter.matches(p))
+            .flat_map(|p| match &p.body {
+                Event::Invocation(invocation) => invocation
+                    .events
+                    .iter()
+                    .flatten()
+                    .map(move |event| (p, event))
+                    .collect(),
+                _ => vec![],
+            })
+            .filter(|(_, event)| {
+                event
+                    .contract_id
+                    .as_ref()
+                    .map(|id| strkey::contract(id.0))
+                    .as_deref()
+                    == Some(self.id.as_str())
+            });
+        page(events, first, skip)
+            .map(|(p, event)| ContractEvent {
+                tx: p.tx.clone(),
+                at: p.at,
+                ledger: p.ledger,
+                event: event.clone(),
+            })
+            .collect()
+    }
+}
+
+pub struct Account {
+    id: String,
+}
+
+#[Object]
+impl Account {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn invocations(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Filter>,
+        first: Option<usize>,
+        skip: Option<usize>,
+    ) -> Vec<Invocation> {
+        let index = ctx.data_unchecked::<SharedIndex>().read().await;
+        select(index.account_records(&self.id), filter, first, skip)
+            .filter_map(Invocation::new)
+            .collect()
+    }
+}
+
+#[derive(Union)]
+pub enum Operation {
+    Invocation(Invocation),
+    Deployment(Deployment),
+}
+
+impl Operation {
+    fn new(processed: Processed) -> Option<Self> {
+        match processed.body {
+            Event::Invocation(_) => Invocation::new(processed).map(Operation::Invocation),
+            Event::Deployment(_) => Deployment::new(processed).map(Operation::Deployment),
+            _ => None,
+        }
+    }
+}
+
+pub struct Invocation {
+    tx: String,
+    source_account: String,
+    at: DateTime<Utc>,
+    ledger: u32,
+    invocation: common::Invocation,
+}
+
+impl Invocation {
+    fn new(processed: Processed) -> Option<Self> {
+        if let Event::Invocation(invocation) = processed.body {
+            Some(Self {
+                tx: processed.tx,
+                source_account: processed.source_account,
+                at: processed.at,
+                ledger: processed.ledger,
+                invocation,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn contract_events(&self) -> Vec<ContractEvent> {
+        self.invocation
+            .events
+            .iter()
+            .flatten()
+            .map(|event| ContractEvent {
+                tx: self.tx.clone(),
+                at: self.at,
+                ledger: self.ledger,
+                event: event.clone(),
+            })
+            .collect()
+    }
+}
+
+#[Object]
+impl Invocation {
+    async fn tx(&self) -> &str {
+        &self.tx
+    }
+
+    async fn source_account(&self) -> &str {
+        &self.source_account
+    }
+
+    async fn at(&self) -> DateTime<Utc> {
+        self.at
+    }
+
+    async fn ledger(&self) -> u32 {
+        self.ledger
+    }
+
+    async fn contract(&self) -> Contract {
+        Contract {
+            id: self.invocation.id.clone(),
+        }
+    }
+
+    async fn function(&self) -> &str {
+        &self.invocation.function
+    }
+
+    async fn args(&self) -> Json<Vec<Option<ScVal>>> {
+        Json(self.invocation.args.clone())
+    }
+
+    async fn result(&self) -> Option<Json<ScVal>> {
+        self.invocation.result.clone().map(Json)
+    }
+
+    async fn error(&self) -> Option<&str> {
+        self.invocation.error.as_deref()
+    }
+
+    async fn footprint(&self) -> Option<Json<LedgerFootprint>> {
+        self.invocation.footprint.clone().map(Json)
+    }
+
+    async fn events(&self) -> Vec<ContractEvent> {
+        self.contract_events()
+    }
+}
+
+pub struct Deployment {
+    tx: String,
+    source_account: String,
+    at: DateTime<Utc>,
+    ledger: u32,
+    id: String,
+    bytes: Vec<u8>,
+}
+
+impl Deployment {
+    fn new(processed: Processed) -> Option<Self> {
+        if let Event::Deployment(deployed) = processed.body {
+            Some(Self {
+                tx: processed.tx,
+                source_account: processed.source_account,
+                at: processed.at,
+                ledger: processed.ledger,
+                id: deployed.id,
+                bytes: deployed.bytes,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[Object]
+impl Deployment {
+    async fn tx(&self) -> &str {
+        &self.tx
+    }
+
+    async fn source_account(&self) -> &str {
+        &self.source_account
+    }
+
+    async fn at(&self) -> DateTime<Utc> {
+        self.at
+    }
+
+    async fn ledger(&self) -> u32 {
+        self.ledger
+    }
+
+    async fn contract(&self) -> Contract {
+        Contract {
+            id: self.id.clone(),
+        }
+    }
+
+    async fn size(&self) -> usize {
+        self.bytes.len()
+    }
+
+    async fn wasm(&self) -> String {
+        hex::encode(&self.bytes)
+    }
+
+    /// Hash, exports, host imports and custom sections of the wasm.
+    async fn wasm_info(&self) -> Option<Json<WasmInfo>> {
+        wasm::inspect(&self.bytes).map(Json)
+    }
+}
+
+pub struct ContractEvent {
+    tx: String,
+    at: DateTime<Utc>,
+    ledger: u32,
+    event: stellar_xdr::ContractEvent,
+}
+
+impl ContractEvent {
+    fn contract_id(&self) -> Option<String> {
+        self.event
+            .contract_id
+            .as_ref()
+            .map(|id| strkey::contract(id.0))
+    }
+}
+
+#[Object]
+impl ContractEvent {
+    async fn tx(&self) -> &str {
+        &self.tx
+    }
+
+    async fn at(&self) -> DateTime<Utc> {
+        self.at
+    }
+
+    async fn ledger(&self) -> u32 {
+        self.ledger
+    }
+
+    #[graphql(name = "contractId")]
+    async fn emitted_by(&self) -> Option<String> {
+        self.contract_id()
+    }
+
+    async fn topics(&self) -> Json<Vec<ScVal>> {
+        let ContractEventBody::V0(body) = &self.event.body;
+        Json(body.topics.iter().cloned().collect())
+    }
+
+    async fn data(&self) -> Json<ScVal> {
+        let ContractEventBody::V0(body) = &self.event.body;
+        Json(body.data.clone())
+    }
+
+    /// Set when the event follows the token interface's `transfer` event layout.
+    async fn transfer(&self) -> Option<TokenTransfer> {
+        let ContractEventBody::V0(body) = &self.event.body;
+        match (body.topics.get(0), body.topics.get(1), body.topics.get(2)) {
+            (Some(ScVal::Symbol(name)), Some(from), Some(to))
+                if name.to_string_lossy() == "transfer" =>
+            {
+                Some(TokenTransfer {
+                    from: Json(from.clone()),
+                    to: Json(to.clone()),
+                    amount: Json(body.data.clone()),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct TokenTransfer {
+    from: Json<ScVal>,
+    to: Json<ScVal>,
+    amount: Json<ScVal>,
+}
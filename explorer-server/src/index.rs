@@ -0,0 +1,244 @@
+use std::collections::{HashMap, HashSet};
+
+use explorer_common::{
+    strkey,
+    types::common::{Event, Processed},
+};
+use serde_derive::Serialize;
+use stellar_xdr::{ContractEventBody, ScObject, ScVal};
+
+#[derive(Default)]
+pub struct Index {
+    processed: Vec<Processed>,
+    // paging tokens of the indexed operations.
+    operations: HashSet<String>,
+    // first operation of each transaction.
+    by_tx: HashMap<String, usize>,
+    by_deployment: HashMap<String, usize>,
+    by_contract: HashMap<String, Vec<usize>>,
+    by_event_contract: HashMap<String, Vec<usize>>,
+    by_account: HashMap<String, Vec<usize>>,
+    by_storage_contract: HashMap<String, Vec<usize>>,
+}
+
+#[derive(Serialize)]
+pub struct Page {
+    pub records: Vec<Processed>,
+    pub next: Option<String>,
+}
+
+impl Index {
+    pub fn insert(&mut self, processed: Processed) {
+        if !self.operations.insert(processed.paging_token.clone()) {
+            return;
+        }
+
+        let pos = self.processed.len();
+        self.by_tx.entry(processed.tx.clone()).or_insert(pos);
+
+        if let Event::Deployment(deployed) = &processed.body {
+            self.by_deployment.insert(deployed.id.clone(), pos);
+        }
+
+        if let Event::Invocation(invocation) = &processed.body {
+            self.by_contract
+                .entry(invocation.id.clone())
+                .or_default()
+                .push(pos);
+            self.by_account
+                .entry(processed.source_account.clone())
+                .or_default()
+                .push(pos);
+
+            if let Some(events) = &invocation.events {
+                for event in events {
+                    if let Some(id) = &event.contract_id {
+                        let positions = self
+                            .by_event_contract
+                            .entry(strkey::contract(id.0))
+                            .or_default();
+                        if positions.last() != Some(&pos) {
+                            positions.push(pos);
+                        }
+                    }
+
+                    // token events name the accounts they move funds between in their topics.
+                    // the pinned XDR has no authorization entries, accounts that only authorized
+                    // an invocation can't be indexed.
+                    let ContractEventBody::V0(body) = &event.body;
+                    for topic in body.topics.iter() {
+                        if let ScVal::Object(Some(ScObject::AccountId(account))) = topic {
+                            let positions = self
+                                .by_account
+                                .entry(strkey::account_id(account))
+                                .or_default();
+                            if positions.last() != Some(&pos) {
+                                positions.push(pos);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for change in &processed.storage_changes {
+            let positions = self
+                .by_storage_contract
+                .entry(change.contract_id.clone())
+                .or_default();
+            if positions.last() != Some(&pos) {
+                positions.push(pos);
+            }
+        }
+
+        self.processed.push(processed);
+    }
+
+    pub fn transaction(&self, hash: &str) -> Option<Processed> {
+        self.by_tx.get(hash).map(|pos| self.processed[*pos].clone())
+    }
+
+    pub fn deployment(&self, id: &str) -> Option<Processed> {
+        self.by_deployment
+            .get(id)
+            .map(|pos| self.processed[*pos].clone())
+    }
+
+    /// Oldest first.
+    pub fn deployments(&self) -> impl Iterator<Item = &Processed> {
+        let mut positions = self.by_deployment.values().copied().collect::<Vec<_>>();
+        positions.sort_unstable();
+        positions.into_iter().map(|pos| &self.processed[pos])
+    }
+
+    pub fn invocations(&self) -> impl Iterator<Item = &Processed> {
+        self.processed
+            .iter()
+            .rev()
+            .filter(|p| matches!(p.body, Event::Invocation(_)))
+    }
+
+    pub fn contract_records(&self, id: &str) -> impl Iterator<Item = &Processed> {
+        self.records(self.by_contract.get(id))
+    }
+
+    pub fn event_records(&self, id: &str) -> impl Iterator<Item = &Processed> {
+        self.records(self.by_event_contract.get(id))
+    }
+
+    pub fn account_records(&self, id: &str) -> impl Iterator<Item = &Processed> {
+        self.records(self.by_account.get(id))
+    }
+
+    pub fn storage_records(&self, id: &str) -> Vec<Processed> {
+        self.records(self.by_storage_contract.get(id))
+            .cloned()
+            .collect()
+    }
+
+    pub fn contract_invocations(&self, id: &str, cursor: Option<usize>, limit: usize) -> Page {
+        self.page(self.by_contract.get(id), cursor, limit)
+    }
+
+    pub fn contract_events(&self, id: &str, cursor: Option<usize>, limit: usize) -> Page {
+        self.page(self.by_event_contract.get(id), cursor, limit)
+    }
+
+    pub fn account_invocations(&self, id: &str, cursor: Option<usize>, limit: usize) -> Page {
+        self.page(self.by_account.get(id), cursor, limit)
+    }
+
+    // newest first.
+    fn records<'a>(
+        &'a self,
+        positions: Option<&'a Vec<usize>>,
+    ) -> impl Iterator<Item = &'a Processed> {
+        positions
+            .into_iter()
+            .flatten()
+            .rev()
+            .map(|pos| &self.processed[*pos])
+    }
+
+    // newest first: the cursor is the position of the last record of the previous page.
+    fn page(&self, positions: Option<&Vec<usize>>, cursor: Option<usize>, limit: usize) -> Page {
+        let positions = match positions {
+            Some(positions) => positions.as_slice(),
+            None => &[],
+        };
+
+        let end = match cursor {
+            Some(cursor) => positions.partition_point(|pos| *pos < cursor),
+            None => positions.len(),
+        };
+        let start = end.saturating_sub(limit);
+
+        Page {
+            records: positions[start..end]
+                .iter()
+                .rev()
+                .map(|pos| self.processed[*pos].clone())
+                .collect(),
+            next: if start > 0 {
+                Some(positions[start].to_string())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use explorer_common::types::common::Invocation;
+
+    use super::*;
+
+    const CONTRACT: &str = "CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O";
+
+    fn invocation(tx: &str, paging_token: &str, function: &str) -> Processed {
+        Processed {
+            source_account: String::from(
+                "GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZX",
+            ),
+            tx: String::from(tx),
+            at: Utc::now(),
+            ledger: 3,
+            application_order: 1,
+            paging_token: String::from(paging_token),
+            fee_bump: None,
+            memo: None,
+            preconditions: None,
+            storage_changes: vec![],
+            body: Event::Invocation(Invocation {
+                id: String::from(CONTRACT),
+                function: String::from(function),
+                args: vec![],
+                result: None,
+                error: None,
+                footprint: None,
+                events: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn operations_of_the_same_transaction() {
+        let mut index = Index::default();
+        index.insert(invocation("tx", "12884905985", "first"));
+        index.insert(invocation("tx", "12884905986", "second"));
+        // the same operation ingested twice.
+        index.insert(invocation("tx", "12884905986", "second"));
+
+        let functions = index
+            .contract_records(CONTRACT)
+            .map(|p| p.body.action())
+            .collect::<Vec<_>>();
+        assert_eq!(functions, ["second", "first"]);
+        assert_eq!(
+            index.transaction("tx").map(|p| p.paging_token).as_deref(),
+            Some("12884905985")
+        );
+    }
+}
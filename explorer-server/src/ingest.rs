@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use explorer_common::{ledgers::get_root, operations::get_operations};
+
+use crate::routes::SharedIndex;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(300);
+
+async fn latest_cursor(horizon: &str) -> String {
+    let url = format!("{horizon}/operations?order=desc&limit=1");
+    let mut retry = POLL_INTERVAL;
+    loop {
+        match get_operations(horizon, None, &url).await {
+            Ok((_, first, _)) => return first.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("ingest: {e}, retrying in {}s", retry.as_secs());
+                tokio::time::sleep(retry).await;
+                retry = (retry * 2).min(MAX_RETRY_INTERVAL);
+            }
+        }
+    }
+}
+
+pub async fn run(horizon: String, cursor: Option<String>, index: SharedIndex) {
+    // deployments are indexed without their derived id when the network isn't known.
+    let network_passphrase = get_root(&horizon).await.map(|root| root.network_passphrase);
+    let cursor = match cursor {
+        Some(cursor) => cursor,
+        None => latest_cursor(&horizon).await,
+    };
+    let mut url = format!("{horizon}/operations?order=asc&limit=200&cursor={cursor}");
+    let mut retry = POLL_INTERVAL;
+
+    loop {
+        let (processed, first, next) =
+            match get_operations(&horizon, network_passphrase.as_deref(), &url).await {
+                Ok(page) => page,
+                // keep the cursor, the page is fetched again once Horizon recovers.
+                Err(e) => {
+                    eprintln!("ingest: {e}, retrying in {}s", retry.as_secs());
+                    tokio::time::sleep(retry).await;
+                    retry = (retry * 2).min(MAX_RETRY_INTERVAL);
+                    continue;
+                }
+            };
+        retry = POLL_INTERVAL;
+
+        {
+            let mut index = index.write().await;
+            for p in processed {
+                index.insert(p);
+            }
+        }
+
+        // an empty page means we caught up with the ledger, wait before asking again.
+        if first.is_some() {
+            url = next;
+        } else {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
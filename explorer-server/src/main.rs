@@ -0,0 +1,32 @@
+#[cfg(feature = "graphql")]
+mod graphql;
+mod index;
+mod ingest;
+mod routes;
+
+use std::{net::SocketAddr, sync::Arc};
+
+use tokio::sync::RwLock;
+
+use index::Index;
+
+const DEFAULT_HORIZON: &str = "https://horizon-futurenet.stellar.org";
+const DEFAULT_LISTEN_ADDR: &str = "0.0.0.0:8080";
+
+#[tokio::main]
+async fn main() {
+    let horizon = std::env::var("HORIZON_URL").unwrap_or_else(|_| String::from(DEFAULT_HORIZON));
+    let addr: SocketAddr = std::env::var("LISTEN_ADDR")
+        .unwrap_or_else(|_| String::from(DEFAULT_LISTEN_ADDR))
+        .parse()
+        .expect("LISTEN_ADDR should be a socket address");
+    let cursor = std::env::var("START_CURSOR").ok();
+
+    let index = Arc::new(RwLock::new(Index::default()));
+    tokio::spawn(ingest::run(horizon, cursor, index.clone()));
+
+    axum::Server::bind(&addr)
+        .serve(routes::router(index).into_make_service())
+        .await
+        .unwrap();
+}
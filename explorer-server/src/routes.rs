@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use explorer_common::{
+    storage::{self, Entry, KeyChange},
+    strkey,
+    types::common::{Event, Processed},
+    verify::{self, Verification},
+};
+use serde_derive::Deserialize;
+use stellar_xdr::{ReadXdr, ScVal};
+use tokio::sync::RwLock;
+use tower_http::cors::CorsLayer;
+
+use crate::index::{Index, Page};
+
+pub type SharedIndex = Arc<RwLock<Index>>;
+
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 200;
+
+#[derive(Deserialize)]
+pub struct Pagination {
+    cursor: Option<usize>,
+    limit: Option<usize>,
+}
+
+impl Pagination {
+    fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct AtLedger {
+    // defaults to the latest indexed ledger.
+    ledger: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct StorageKey {
+    // base64 XDR `ScVal`.
+    key: String,
+}
+
+pub fn router(index: SharedIndex) -> Router {
+    let router = Router::new()
+        .route("/contracts/:id/invocations", get(contract_invocations))
+        .route("/contracts/:id/events", get(contract_events))
+        .route("/contracts/:id/storage", get(contract_storage))
+        .route("/contracts/:id/storage/history", get(storage_history))
+        .route("/contracts/:id/verify", post(verify_wasm))
+        .route("/tx/:hash", get(transaction))
+        .route("/accounts/:id/invocations", get(account_invocations));
+
+    #[cfg(feature = "graphql")]
+    let router = router.merge(crate::graphql::router(index.clone()));
+
+    router.layer(CorsLayer::permissive()).with_state(index)
+}
+
+async fn contract_invocations(
+    State(index): State<SharedIndex>,
+    Path(id): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> Json<Page> {
+    let id = strkey::normalize_contract_id(&id).unwrap_or(id);
+    Json(
+        index
+            .read()
+            .await
+            .contract_invocations(&id, pagination.cursor, pagination.limit()),
+    )
+}
+
+async fn contract_events(
+    State(index): State<SharedIndex>,
+    Path(id): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> Json<Page> {
+    let id = strkey::normalize_contract_id(&id).unwrap_or(id);
+    Json(
+        index
+            .read()
+            .await
+            .contract_events(&id, pagination.cursor, pagination.limit()),
+    )
+}
+
+async fn contract_storage(
+    State(index): State<SharedIndex>,
+    Path(id): Path<String>,
+    Query(at): Query<AtLedger>,
+) -> Json<Vec<Entry>> {
+    let id = strkey::normalize_contract_id(&id).unwrap_or(id);
+    let records = index.read().await.storage_records(&id);
+    Json(storage::snapshot(
+        &id,
+        &records,
+        at.ledger.unwrap_or(u32::MAX),
+    ))
+}
+
+async fn storage_history(
+    State(index): State<SharedIndex>,
+    Path(id): Path<String>,
+    Query(key): Query<StorageKey>,
+) -> Result<Json<Vec<KeyChange>>, StatusCode> {
+    let id = strkey::normalize_contract_id(&id).unwrap_or(id);
+    let key = ScVal::from_xdr_base64(&key.key).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let records = index.read().await.storage_records(&id);
+    Ok(Json(storage::key_history(&id, &records, &key)))
+}
+
+// the body is the locally built wasm.
+async fn verify_wasm(
+    State(index): State<SharedIndex>,
+    Path(id): Path<String>,
+    wasm: Bytes,
+) -> Result<Json<Verification>, StatusCode> {
+    let id = strkey::normalize_contract_id(&id).unwrap_or(id);
+    let deployed = match index.read().await.deployment(&id).map(|p| p.body) {
+        Some(Event::Deployment(deployed)) => deployed,
+        _ => return Err(StatusCode::NOT_FOUND),
+    };
+    verify::verify(&wasm, &deployed)
+        .map(Json)
+        .ok_or(StatusCode::UNPROCESSABLE_ENTITY)
+}
+
+async fn account_invocations(
+    State(index): State<SharedIndex>,
+    Path(id): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> Json<Page> {
+    Json(
+        index
+            .read()
+            .await
+            .account_invocations(&id, pagination.cursor, pagination.limit()),
+    )
+}
+
+async fn transaction(
+    State(index): State<SharedIndex>,
+    Path(hash): Path<String>,
+) -> Result<Json<Processed>, StatusCode> {
+    match index.read().await.transaction(&hash) {
+        Some(processed) => Ok(Json(processed)),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
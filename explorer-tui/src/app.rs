@@ -0,0 +1,159 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use explorer_common::types::common::Processed;
+use ratatui::widgets::ListState;
+
+use crate::fetch::Message;
+
+const TAIL_LEN: usize = 50;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    List,
+    Detail,
+}
+
+pub struct App {
+    pub contract_id: String,
+    pub invocations: Vec<Processed>,
+    pub tail: Vec<Processed>,
+    pub search: String,
+    pub searching: bool,
+    pub loading: bool,
+    pub focus: Focus,
+    pub list_state: ListState,
+    pub detail_scroll: u16,
+}
+
+impl App {
+    pub fn new(contract_id: String) -> Self {
+        Self {
+            contract_id,
+            invocations: vec![],
+            tail: vec![],
+            search: String::new(),
+            searching: false,
+            loading: true,
+            focus: Focus::List,
+            list_state: ListState::default(),
+            detail_scroll: 0,
+        }
+    }
+
+    pub fn filtered(&self) -> Vec<&Processed> {
+        let search = self.search.to_lowercase();
+        self.invocations
+            .iter()
+            .filter(|p| {
+                search.is_empty()
+                    || p.tx.contains(&search)
+                    || p.source_account.to_lowercase().contains(&search)
+                    || p.body.action().to_lowercase().contains(&search)
+            })
+            .collect()
+    }
+
+    pub fn selected(&self) -> Option<&Processed> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.filtered().get(i).copied())
+    }
+
+    pub fn receive(&mut self, msg: Message) {
+        match msg {
+            Message::History(mut processed) => {
+                self.invocations.append(&mut processed);
+                // history pages can arrive after newer tail records, keep newest first.
+                self.invocations.sort_by(|a, b| {
+                    (b.ledger, b.application_order).cmp(&(a.ledger, a.application_order))
+                });
+                if self.list_state.selected().is_none() && !self.filtered().is_empty() {
+                    self.list_state.select(Some(0));
+                }
+            }
+            Message::HistoryDone => self.loading = false,
+            Message::Tail(processed) => {
+                let added = processed.len();
+                for p in processed {
+                    self.tail.insert(0, p.clone());
+                    self.invocations.insert(0, p);
+                }
+                self.tail.truncate(TAIL_LEN);
+
+                // keep the cursor on the invocation that was being looked at.
+                match self.list_state.selected() {
+                    Some(i) if self.search.is_empty() => self.list_state.select(Some(i + added)),
+                    None => self.list_state.select(Some(0)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn select(&mut self, delta: isize) {
+        let len = self.filtered().len();
+        if len == 0 {
+            self.list_state.select(None);
+            return;
+        }
+
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, len as isize - 1) as usize;
+        self.list_state.select(Some(next));
+        self.detail_scroll = 0;
+    }
+
+    fn scroll(&mut self, delta: i32) {
+        self.detail_scroll = (self.detail_scroll as i32 + delta).max(0) as u16;
+    }
+
+    fn on_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.search.clear();
+                self.searching = false;
+            }
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Char(c) => self.search.push(c),
+            _ => return,
+        }
+
+        self.list_state.select(if self.filtered().is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+        self.detail_scroll = 0;
+    }
+
+    /// Returns `false` when the app should quit.
+    pub fn on_key(&mut self, key: KeyEvent) -> bool {
+        if self.searching {
+            self.on_search_key(key);
+            return true;
+        }
+
+        match (key.code, self.focus) {
+            (KeyCode::Char('q'), _) => return false,
+            (KeyCode::Char('/'), _) => {
+                self.searching = true;
+                self.focus = Focus::List;
+            }
+            (KeyCode::Tab, Focus::List) => self.focus = Focus::Detail,
+            (KeyCode::Tab, Focus::Detail) => self.focus = Focus::List,
+            (KeyCode::Down | KeyCode::Char('j'), Focus::List) => self.select(1),
+            (KeyCode::Up | KeyCode::Char('k'), Focus::List) => self.select(-1),
+            (KeyCode::Down | KeyCode::Char('j'), Focus::Detail) => self.scroll(1),
+            (KeyCode::Up | KeyCode::Char('k'), Focus::Detail) => self.scroll(-1),
+            (KeyCode::PageDown, _) => self.scroll(10),
+            (KeyCode::PageUp, _) => self.scroll(-10),
+            (KeyCode::Char('g'), Focus::List) => self.select(isize::MIN / 2),
+            (KeyCode::Char('G'), Focus::List) => self.select(isize::MAX / 2),
+            _ => {}
+        }
+
+        true
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use explorer_common::{
+    ledgers::get_root,
+    operations::{get_contract_operations, get_operations},
+    types::common::Processed,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+const HISTORY_PAGES: usize = 20;
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+pub enum Message {
+    History(Vec<Processed>),
+    Tail(Vec<Processed>),
+    HistoryDone,
+}
+
+pub async fn history(horizon: String, contract_id: String, tx: UnboundedSender<Message>) {
+    let network_passphrase = get_root(&horizon).await.map(|root| root.network_passphrase);
+    let mut url = format!("{horizon}/operations?order=desc&limit=200");
+
+    for _ in 0..HISTORY_PAGES {
+        let (processed, first, next) = match get_contract_operations(
+            &horizon,
+            network_passphrase.as_deref(),
+            &url,
+            &contract_id,
+        )
+        .await
+        {
+            Ok(page) => page,
+            Err(_) => break,
+        };
+        if tx.send(Message::History(processed)).is_err() {
+            return;
+        }
+        if first.is_none() {
+            break;
+        }
+        url = next;
+    }
+
+    let _ = tx.send(Message::HistoryDone);
+}
+
+pub async fn tail(horizon: String, contract_id: String, tx: UnboundedSender<Message>) {
+    let network_passphrase = get_root(&horizon).await.map(|root| root.network_passphrase);
+    let latest = format!("{horizon}/operations?order=desc&limit=1");
+    let cursor = loop {
+        match get_operations(&horizon, None, &latest).await {
+            Ok((_, first, _)) => break first.unwrap_or_default(),
+            Err(_) => tokio::time::sleep(POLL_INTERVAL).await,
+        }
+    };
+    let mut url = format!("{horizon}/operations?order=asc&limit=200&cursor={cursor}");
+
+    loop {
+        let (processed, first, next) = match get_contract_operations(
+            &horizon,
+            network_passphrase.as_deref(),
+            &url,
+            &contract_id,
+        )
+        .await
+        {
+            Ok(page) => page,
+            // retry the same page later.
+            Err(_) => {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+        };
+        if !processed.is_empty() && tx.send(Message::Tail(processed)).is_err() {
+            return;
+        }
+
+        if first.is_some() {
+            url = next;
+        } else {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
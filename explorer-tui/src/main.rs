@@ -0,0 +1,77 @@
+mod app;
+mod fetch;
+mod ui;
+
+use std::{io, time::Duration};
+
+use crossterm::{
+    event::{self, Event},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    Terminal,
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use app::App;
+use fetch::Message;
+
+const DEFAULT_HORIZON: &str = "https://horizon-futurenet.stellar.org";
+const TICK: Duration = Duration::from_millis(200);
+
+fn run<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut app: App,
+    mut rx: UnboundedReceiver<Message>,
+) -> io::Result<()> {
+    loop {
+        while let Ok(msg) = rx.try_recv() {
+            app.receive(msg);
+        }
+
+        terminal.draw(|f| ui::draw(f, &mut app))?;
+
+        if event::poll(TICK)? {
+            if let Event::Key(key) = event::read()? {
+                if !app.on_key(key) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+    let contract_id = match std::env::args().nth(1) {
+        Some(id) => id,
+        None => {
+            eprintln!("usage: explorer-tui <contract id>");
+            std::process::exit(1);
+        }
+    };
+    let horizon = std::env::var("HORIZON_URL").unwrap_or_else(|_| String::from(DEFAULT_HORIZON));
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(fetch::history(
+        horizon.clone(),
+        contract_id.clone(),
+        tx.clone(),
+    ));
+    tokio::spawn(fetch::tail(horizon, contract_id.clone(), tx));
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = run(&mut terminal, App::new(contract_id), rx);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    result
+}
@@ -0,0 +1,131 @@
+use explorer_common::{
+    types::common::{Event, Processed},
+    wasm,
+};
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::{App, Focus};
+
+fn summary(processed: &Processed) -> String {
+    let action = processed.body.action();
+    format!(
+        "{}  {}  {}",
+        processed.at,
+        action,
+        &processed.tx[..8.min(processed.tx.len())]
+    )
+}
+
+fn pretty<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap()
+}
+
+fn detail(processed: &Processed) -> String {
+    let mut out = format!(
+        "tx: {}\nat: {}\nsource: {}\n",
+        processed.tx, processed.at, processed.source_account
+    );
+
+    match &processed.body {
+        Event::Invocation(invocation) => {
+            out += &format!(
+                "contract: {}\nfunction: {}\n",
+                invocation.id, invocation.function
+            );
+            out += "\nargs:\n";
+            for (i, arg) in invocation.args.iter().enumerate() {
+                out += &format!("[{i}] {}\n", pretty(arg));
+            }
+            out += &format!("\nresult:\n{}\n", pretty(&invocation.result));
+            out += &format!("\nevents:\n{}\n", pretty(&invocation.events));
+            out += &format!("\nfootprint:\n{}\n", pretty(&invocation.footprint));
+        }
+        Event::Deployment(deployed) => {
+            out += &format!(
+                "deployed contract: {}\nwasm size: {} bytes\n",
+                deployed.id,
+                deployed.bytes.len()
+            );
+            match wasm::inspect(&deployed.bytes) {
+                Some(info) => out += &format!("\nwasm:\n{}\n", pretty(&info)),
+                None => out += "\nwasm: invalid module\n",
+            }
+        }
+        event => {
+            out += &format!("operation: {}\n{}\n", event.action(), pretty(event));
+        }
+    }
+
+    out
+}
+
+fn block(title: &str, focused: bool) -> Block<'_> {
+    let style = if focused {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(style)
+        .title(title)
+}
+
+pub fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(10),
+            Constraint::Length(8),
+            Constraint::Length(1),
+        ])
+        .split(f.size());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[0]);
+
+    let items = app
+        .filtered()
+        .into_iter()
+        .map(|p| ListItem::new(summary(p)))
+        .collect::<Vec<_>>();
+    let title = format!(
+        "{} invocations{}",
+        app.contract_id,
+        if app.loading { " (loading...)" } else { "" }
+    );
+    let list = List::new(items)
+        .block(block(&title, app.focus == Focus::List))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    f.render_stateful_widget(list, columns[0], &mut app.list_state);
+
+    let text = app.selected().map(detail).unwrap_or_default();
+    let details = Paragraph::new(text)
+        .block(block("details", app.focus == Focus::Detail))
+        .wrap(Wrap { trim: false })
+        .scroll((app.detail_scroll, 0));
+    f.render_widget(details, columns[1]);
+
+    let tail = app
+        .tail
+        .iter()
+        .map(|p| ListItem::new(summary(p)))
+        .collect::<Vec<_>>();
+    f.render_widget(List::new(tail).block(block("live", false)), rows[1]);
+
+    let status = if app.searching {
+        format!("/{}", app.search)
+    } else if !app.search.is_empty() {
+        format!("filter: {}  (/ edit, esc in search clears)", app.search)
+    } else {
+        String::from("q quit  / search  tab switch pane  j/k move  pgup/pgdn scroll")
+    };
+    f.render_widget(Paragraph::new(status), rows[2]);
+}
@@ -1,10 +1,18 @@
 use explorer_common::{
+    contract_id::FUTURENET_PASSPHRASE,
+    invoke::{invoke_command, FUTURENET_RPC_URL},
     operations::get_contract_operations,
-    types::{common::Processed, transaction::Response},
+    spec,
+    transactions::{get_transaction, process_tx_operations},
+    types::{
+        common::{Event, Processed},
+        transaction::Response,
+    },
 };
 
 use log::info;
 use stdweb::js;
+use stellar_xdr::ScSpecEntry;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
@@ -14,6 +22,8 @@ pub struct App {
     contract_id: String,
     processed_ops: Vec<Option<Processed>>,
     next: String,
+    // spec of the contract, read from its deployment.
+    spec: Vec<ScSpecEntry>,
 }
 
 pub enum AppMsg {
@@ -21,6 +31,8 @@ pub enum AppMsg {
     ProcessedOps(Vec<Option<Processed>>),
     NextHref(String),
     LoadMore,
+    DeploymentURL(String),
+    Spec(Vec<ScSpecEntry>),
 }
 
 async fn read_and_process_ops(
@@ -36,9 +48,23 @@ async fn read_and_process_ops(
     }
 
     while out.len() == 0 {
-        let processed =
-            get_contract_operations("https://horizon-futurenet.stellar.org", &url, id.as_str())
-                .await;
+        let processed = match get_contract_operations(
+            "https://horizon-futurenet.stellar.org",
+            Some(FUTURENET_PASSPHRASE),
+            &url,
+            id.as_str(),
+        )
+        .await
+        {
+            Ok(processed) => processed,
+            Err(e) => {
+                let message = e.to_string();
+                js! {
+                document.getElementById("status").innerText = @{message}
+                }
+                return;
+            }
+        };
         out = processed
             .0
             .into_iter()
@@ -56,6 +82,44 @@ async fn read_and_process_ops(
     f(out);
 }
 
+async fn read_spec(f: impl Fn(Vec<ScSpecEntry>), id: String) {
+    let processed = match get_transaction("https://horizon-futurenet.stellar.org/", &id).await {
+        Some(tx) => process_tx_operations(tx, Some(FUTURENET_PASSPHRASE)),
+        None => return,
+    };
+    let spec = processed.iter().find_map(|p| match &p.body {
+        Event::Deployment(deployed) => spec::from_wasm(&deployed.bytes),
+        _ => None,
+    });
+
+    if let Some(spec) = spec {
+        f(spec);
+    }
+}
+
+fn invoke_view(spec: &[ScSpecEntry], processed: &Processed) -> Html {
+    let invocation = match &processed.body {
+        Event::Invocation(invocation) => invocation,
+        _ => return html! {},
+    };
+    // without the spec, arguments are passed as XDR.
+    let command = invoke_command(invocation, spec, FUTURENET_RPC_URL, FUTURENET_PASSPHRASE);
+    let text = command.clone();
+    let onclick = Callback::from(move |_: MouseEvent| {
+        let text = text.clone();
+        js! {
+        navigator.clipboard.writeText(@{text})
+        }
+    });
+
+    html! {
+    <div class="invoke">
+    <button type="button" onclick={onclick}>{"Copy soroban-cli command"}</button>
+    <pre><code class="language-bash">{command}</code></pre>
+    </div>
+    }
+}
+
 trait Extend {
     fn read_id(&self) -> &str;
 }
@@ -128,6 +192,17 @@ impl Component for App {
 
                 true
             }
+            AppMsg::DeploymentURL(id) => {
+                let link = ctx.link().clone();
+                wasm_bindgen_futures::spawn_local(async {
+                    read_spec(move |spec| link.send_message(AppMsg::Spec(spec)), id).await;
+                });
+                false
+            }
+            AppMsg::Spec(spec) => {
+                self.spec = spec;
+                true
+            }
         }
     }
 
@@ -143,6 +218,12 @@ impl Component for App {
         });
 
         let loadmore = Callback::from(move |e: MouseEvent| link1.send_message(AppMsg::LoadMore));
+        let link = ctx.link().clone();
+        let on_deployment_input = Callback::from(move |e: InputEvent| {
+            let target = e.target().unwrap();
+            let input = target.unchecked_into::<HtmlInputElement>();
+            link.send_message(AppMsg::DeploymentURL(input.value()))
+        });
 
         html! {
                             <main>
@@ -152,25 +233,24 @@ impl Component for App {
         <p style="width: 600px;text-align:left;color:#7c7c7c">{ "Paste below your contract's id and see its invocations. Might take a bit if the contract hasn't been invoked in some time. To load more invocations click on \"Load More Operations\". Check out the " }<a href="https://github.com/xycloo/soroban-explorer/tree/main/web">{"repo"}</a>{"."}</p>
                                 <div>
                     <input oninput={oninput} />
+                    <input oninput={on_deployment_input} placeholder="deployment tx, to format invoke arguments" />
 
                     </div>
                     </div>
 
                     {
                         for processed_operations.into_iter().map(|e| {
-                    let action = match e.clone().unwrap().body {
-                    explorer_common::types::common::Event::Invocation(invocation) => invocation.function,
-                    explorer_common::types::common::Event::Deployment(_) => String::from("deploy"),
-                    };
+                    let action = e.clone().unwrap().body.action();
                     html! {
                     <div>
                     <button type="button" class="collapsible">{&action}</button>
                     <div class="content">
                     <pre><code class="language-json">
                         {
-                    serde_json::to_string_pretty(&e.unwrap()).unwrap()
+                    serde_json::to_string_pretty(e.as_ref().unwrap()).unwrap()
                         }
                 </code></pre>
+                    {invoke_view(&self.spec, e.as_ref().unwrap())}
                     </div>
                         </div>
                     }
@@ -1,31 +1,41 @@
 use explorer_common::{
-    transactions::{get_transaction, process_tx},
-    types::{common::Processed, transaction::Response},
+    contract_id::FUTURENET_PASSPHRASE,
+    invoke::{invoke_command, FUTURENET_RPC_URL},
+    spec,
+    transactions::{get_transaction, process_tx_operations},
+    types::{
+        common::{Deployed, Event, Invocation, Processed},
+        transaction::Response,
+    },
+    wasm,
 };
 use log::info;
-use wasm_bindgen::{JsCast, JsValue};
-use web_sys::HtmlInputElement;
+use stellar_xdr::ScSpecEntry;
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, HtmlInputElement, Url};
 use yew::prelude::*;
 
 #[derive(Default)]
 pub struct App {
     transaction: String,
     processed_tx: Vec<Option<Processed>>,
+    // spec of the invoked contract, read from its deployment.
+    spec: Vec<ScSpecEntry>,
 }
 
 pub enum AppMsg {
     TransactionURL(String),
-    ProcessedTx(Processed),
+    ProcessedTx(Vec<Processed>),
+    DeploymentURL(String),
+    Spec(Vec<ScSpecEntry>),
 }
 
-async fn read_and_process_tx(f: impl Fn(Processed), id: String) {
-    let processed = process_tx(
-        get_transaction("https://horizon-futurenet.stellar.org/", &id)
-            .await
-            .unwrap(),
-    );
+async fn read_and_process_tx(f: impl Fn(Vec<Processed>), id: String) {
+    let processed = match get_transaction("https://horizon-futurenet.stellar.org/", &id).await {
+        Some(tx) => process_tx_operations(tx, Some(FUTURENET_PASSPHRASE)),
+        None => return,
+    };
 
-    let object = JsValue::from(processed.clone().source_account);
     f(processed);
     //    extern crate stdweb;
     //    use stdweb::js;
@@ -34,6 +44,141 @@ async fn read_and_process_tx(f: impl Fn(Processed), id: String) {
     //    }
 }
 
+async fn read_spec(f: impl Fn(Vec<ScSpecEntry>), id: String) {
+    let processed = match get_transaction("https://horizon-futurenet.stellar.org/", &id).await {
+        Some(tx) => process_tx_operations(tx, Some(FUTURENET_PASSPHRASE)),
+        None => return,
+    };
+    let spec = processed.iter().find_map(|p| match &p.body {
+        Event::Deployment(deployed) => spec::from_wasm(&deployed.bytes),
+        _ => None,
+    });
+
+    if let Some(spec) = spec {
+        f(spec);
+    }
+}
+
+fn failure_view(spec: &[ScSpecEntry], invocation: &Invocation) -> Html {
+    let errors = spec::invocation_errors(spec, invocation);
+    if invocation.error.is_none() && errors.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="failure">
+            <h2>{ format!("{} failed", invocation.function) }</h2>
+            if let Some(error) = &invocation.error {
+                <>
+                <p>{ error }</p>
+                <p class="note">{ spec::UNKNOWN_FAILURE_CODE }</p>
+                </>
+            }
+            {for errors.iter().map(|error| html! {
+                <p class="error">
+                    { format!("{}::{} ({})", error.enum_name, error.name, error.code) }
+                </p>
+            })}
+        </div>
+    }
+}
+
+fn copy(text: &str) {
+    // `Clipboard` is behind web-sys' unstable APIs, call it through reflection.
+    let navigator = web_sys::window().unwrap().navigator();
+    let clipboard = js_sys::Reflect::get(&navigator, &"clipboard".into()).unwrap();
+    let write_text = js_sys::Reflect::get(&clipboard, &"writeText".into()).unwrap();
+    let _ = write_text
+        .unchecked_into::<js_sys::Function>()
+        .call1(&clipboard, &text.into());
+}
+
+fn invoke_view(spec: &[ScSpecEntry], invocation: &Invocation) -> Html {
+    let command = invoke_command(invocation, spec, FUTURENET_RPC_URL, FUTURENET_PASSPHRASE);
+    let text = command.clone();
+    let onclick = Callback::from(move |_: MouseEvent| copy(&text));
+
+    html! {
+        <div class="invoke">
+            <button onclick={onclick}>{ "copy soroban-cli command" }</button>
+            <pre><code class="language-bash">{ command }</code></pre>
+        </div>
+    }
+}
+
+fn download(name: &str, bytes: &[u8]) {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let mut options = BlobPropertyBag::new();
+    options.type_("application/wasm");
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options).unwrap();
+    let url = Url::create_object_url_with_blob(&blob).unwrap();
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let link = document
+        .create_element("a")
+        .unwrap()
+        .unchecked_into::<HtmlAnchorElement>();
+    link.set_href(&url);
+    link.set_download(name);
+    link.click();
+    let _ = Url::revoke_object_url(&url);
+}
+
+fn wasm_view(deployed: &Deployed) -> Html {
+    let info = match wasm::inspect(&deployed.bytes) {
+        Some(info) => info,
+        None => return html! { <p class="error">{ "the deployed code isn't valid wasm" }</p> },
+    };
+
+    let name = format!("{}.wasm", deployed.id);
+    let bytes = deployed.bytes.clone();
+    let onclick = Callback::from(move |_: MouseEvent| download(&name, &bytes));
+
+    let functions = wasm::functions(&deployed.bytes).unwrap_or_default();
+    let (bodies, rest) = wasm::disassemble(&deployed.bytes)
+        .map(|wat| wasm::split_functions(&wat))
+        .unwrap_or_default();
+
+    html! {
+        <div class="wasm">
+        <h2>{ format!("wasm {}", info.hash) }</h2>
+        <button onclick={onclick}>{ "download .wasm" }</button>
+        <pre><code class="language-json">{ serde_json::to_string_pretty(&info).unwrap() }</code></pre>
+
+        <details open={true}>
+        <summary>{ "interface" }</summary>
+        <pre><code>{ spec::interface(&info.spec) }</code></pre>
+        <details>
+        <summary>{ "TypeScript declarations" }</summary>
+        <pre><code>{ spec::typescript_declarations(&info.spec) }</code></pre>
+        </details>
+        <details>
+        <summary>{ "Rust declarations" }</summary>
+        <pre><code>{ spec::rust_declarations(&info.spec) }</code></pre>
+        </details>
+        </details>
+
+        <details>
+        <summary>{ format!("WAT ({} functions)", functions.len()) }</summary>
+        <ul class="functions">
+        {for functions.iter().map(|function| html! {
+            <li>
+            <a href={format!("#func-{}", function.index)}>
+            { function.name.clone().unwrap_or_else(|| format!("func {}", function.index)) }
+            </a>
+            { format!(" {} bytes", function.size) }
+            </li>
+        })}
+        </ul>
+        {for functions.iter().zip(bodies).map(|(function, body)| html! {
+            <pre id={format!("func-{}", function.index)}><code>{ body }</code></pre>
+        })}
+        <pre><code>{ rest }</code></pre>
+        </details>
+        </div>
+    }
+}
+
 trait Extend {
     fn read_id(&self) -> &str;
 }
@@ -57,7 +202,7 @@ impl Component for App {
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             AppMsg::ProcessedTx(processed) => {
-                self.processed_tx = vec![Some(processed)];
+                self.processed_tx = processed.into_iter().map(Some).collect();
                 true
             }
             AppMsg::TransactionURL(id) => {
@@ -71,6 +216,17 @@ impl Component for App {
                 });
                 true
             }
+            AppMsg::DeploymentURL(id) => {
+                let link = ctx.link().clone();
+                wasm_bindgen_futures::spawn_local(async {
+                    read_spec(move |spec| link.send_message(AppMsg::Spec(spec)), id).await;
+                });
+                false
+            }
+            AppMsg::Spec(spec) => {
+                self.spec = spec;
+                true
+            }
         }
     }
 
@@ -83,6 +239,12 @@ impl Component for App {
             let input = target.unchecked_into::<HtmlInputElement>();
             link.send_message(AppMsg::TransactionURL(input.value()))
         });
+        let link = ctx.link().clone();
+        let on_deployment_input = Callback::from(move |e: InputEvent| {
+            let target = e.target().unwrap();
+            let input = target.unchecked_into::<HtmlInputElement>();
+            link.send_message(AppMsg::DeploymentURL(input.value()))
+        });
 
         html! {
                 <main>
@@ -91,6 +253,7 @@ impl Component for App {
                     <p>{ "made with " }<span class="heart"></span> { " by " } <a href="https://github.com/xycloo">{ "Xycloo" }</a></p>
                     <div>
         <input oninput={oninput} />
+        <input oninput={on_deployment_input} placeholder="deployment tx, to name contract errors" />
 
         </div>
         </div>
@@ -101,6 +264,17 @@ impl Component for App {
 
 
         } </code></pre>
+
+        {for self.processed_tx.iter().flatten().filter_map(|p| match &p.body {
+            Event::Invocation(invocation) => Some(html! {
+                <>
+                { failure_view(&self.spec, invocation) }
+                { invoke_view(&self.spec, invocation) }
+                </>
+            }),
+            Event::Deployment(deployed) => Some(wasm_view(deployed)),
+            _ => None,
+        })}
         </main>
 
             }
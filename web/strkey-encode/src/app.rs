@@ -1,29 +1,130 @@
+use explorer_common::{
+    contract_id::{self, FUTURENET_PASSPHRASE},
+    strkey::{self, Kind},
+};
 use log::info;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
 use hex;
-use stellar_strkey::*;
 
 #[derive(Default)]
 pub struct App {
-    hash: String,
-    strkey: String,
+    input: String,
+    representations: Vec<(String, String)>,
+    error: Option<String>,
+    deployer: String,
+    salt: String,
+    asset: String,
 }
 
 pub enum AppMsg {
-    Hash(String),
-    Encoded(String),
+    Input(String),
+    Deployer(String),
+    Salt(String),
+    Asset(String),
 }
 
-fn read_and_process_tx(f: impl Fn(String), id: String) {
-    let encoded = Strkey::Contract(stellar_strkey::Contract(
-        hex::decode(id).unwrap().as_slice().try_into().unwrap(),
-    ))
-    .to_string();
+fn from_strkey(input: &str) -> Option<Vec<(String, String)>> {
+    let key = strkey::decode(input)?;
+    let payload = strkey::payload(&key);
 
-    f(encoded);
+    let mut out = vec![
+        (String::from("kind"), format!("{:?}", Kind::of(&key))),
+        (String::from("strkey"), key.to_string()),
+        (String::from("hex"), hex::encode(&payload)),
+    ];
+
+    if let Ok(bytes) = payload.as_slice().try_into() {
+        out.push((String::from("account strkey (G)"), strkey::account(bytes)));
+        out.push((String::from("contract strkey (C)"), strkey::contract(bytes)));
+    }
+
+    if let Some(xdr) = strkey::to_sc_val_xdr(input) {
+        out.push((String::from("ScVal XDR"), xdr));
+    }
+
+    Some(out)
+}
+
+fn from_hex(input: &str) -> Result<Vec<(String, String)>, String> {
+    let bytes = hex::decode(input).map_err(|_| String::from("odd number of hex digits"))?;
+    let bytes: [u8; 32] = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| format!("expected 32 bytes of hex, got {}", bytes.len()))?;
+
+    let account = strkey::account(bytes);
+    let contract = strkey::contract(bytes);
+    let mut out = vec![
+        (String::from("hex"), hex::encode(bytes)),
+        (String::from("account strkey (G)"), account.clone()),
+        (String::from("contract strkey (C)"), contract.clone()),
+    ];
+
+    if let Some(xdr) = strkey::to_sc_val_xdr(&account) {
+        out.push((String::from("account ScVal XDR"), xdr));
+    }
+    if let Some(xdr) = strkey::to_sc_val_xdr(&contract) {
+        out.push((String::from("contract ScVal XDR"), xdr));
+    }
+
+    Ok(out)
+}
+
+fn convert(input: &str) -> Result<Vec<(String, String)>, String> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Ok(vec![]);
+    }
+
+    if let Some(out) = from_strkey(input) {
+        return Ok(out);
+    }
+
+    if input.chars().all(|c| c.is_ascii_hexdigit()) {
+        return from_hex(input);
+    }
+
+    if let Some(out) = strkey::from_sc_val_xdr(input).and_then(|key| from_strkey(&key)) {
+        return Ok(out);
+    }
+
+    match input.chars().next() {
+        Some('G' | 'C' | 'S' | 'M' | 'T' | 'X' | 'P') if input.len() >= 56 => Err(String::from(
+            "invalid strkey: wrong length, version byte or checksum",
+        )),
+        _ => Err(String::from(
+            "unrecognized input: expected 32 bytes of hex, a strkey or a base64 ScVal address",
+        )),
+    }
+}
+
+fn derived_from_deployer(deployer: &str, salt: &str) -> Result<String, String> {
+    let account = strkey::to_account_id(deployer.trim())
+        .ok_or_else(|| String::from("the deployer must be a G... strkey"))?;
+    let salt = contract_id::parse_bytes32(salt)
+        .ok_or_else(|| String::from("the salt must be 32 bytes of hex"))?;
+
+    contract_id::from_source_account(FUTURENET_PASSPHRASE, &account, salt)
+        .ok_or_else(|| String::from("could not derive the contract id"))
+}
+
+fn derived_from_asset(asset: &str) -> Result<String, String> {
+    let asset = contract_id::parse_asset(asset.trim())
+        .ok_or_else(|| String::from("the asset must be `native` or `CODE:ISSUER`"))?;
+
+    contract_id::from_asset(FUTURENET_PASSPHRASE, &asset)
+        .ok_or_else(|| String::from("could not derive the contract id"))
+}
+
+fn derived_view(derived: Result<String, String>) -> Html {
+    match derived {
+        Ok(id) => html! { <pre><code>{ id }</code></pre> },
+        Err(error) => html! { <p class="error">{ error }</p> },
+    }
 }
 
 trait Extend {
@@ -32,7 +133,7 @@ trait Extend {
 
 impl Extend for App {
     fn read_id(&self) -> &str {
-        &self.hash
+        &self.input
     }
 }
 
@@ -48,45 +149,103 @@ impl Component for App {
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            AppMsg::Encoded(encoded) => {
-                self.strkey = encoded;
+            AppMsg::Input(input) => {
+                match convert(&input) {
+                    Ok(representations) => {
+                        self.representations = representations;
+                        self.error = None;
+                    }
+                    Err(error) => {
+                        self.representations = vec![];
+                        self.error = Some(error);
+                    }
+                }
+                self.input = input;
+
                 true
             }
-            AppMsg::Hash(hash) => {
-                let link = ctx.link().clone();
-                read_and_process_tx(
-                    move |encoded| link.send_message(AppMsg::Encoded(encoded)),
-                    hash,
-                );
-
+            AppMsg::Deployer(deployer) => {
+                self.deployer = deployer;
+                true
+            }
+            AppMsg::Salt(salt) => {
+                self.salt = salt;
+                true
+            }
+            AppMsg::Asset(asset) => {
+                self.asset = asset;
                 true
             }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let processed_transaction = self.strkey.clone();
-        let link = ctx.link().clone();
-
-        let oninput = Callback::from(move |e: InputEvent| {
-            let target = e.target().unwrap();
-            let input = target.unchecked_into::<HtmlInputElement>();
-            link.send_message(AppMsg::Hash(input.value()))
-        });
+        let input_callback = |msg: fn(String) -> AppMsg| {
+            let link = ctx.link().clone();
+            Callback::from(move |e: InputEvent| {
+                let target = e.target().unwrap();
+                let input = target.unchecked_into::<HtmlInputElement>();
+                link.send_message(msg(input.value()))
+            })
+        };
+
+        let oninput = input_callback(AppMsg::Input);
+        let ondeployer = input_callback(AppMsg::Deployer);
+        let onsalt = input_callback(AppMsg::Salt);
+        let onasset = input_callback(AppMsg::Asset);
 
         html! {
                 <main>
         <div id="heading">
-                    <h1>{ "Hex to StrKey" }</h1>
-                    <p>{ "Convert soroban contract hashes to StrKey. Made with " }<span class="heart"></span> { " by " } <a href="https://github.com/xycloo">{ "Xycloo" }</a></p>
+                    <h1>{ "StrKey converter" }</h1>
+                    <p>{ "Paste a hex hash, any strkey or a base64 ScVal address and get all its representations. Made with " }<span class="heart"></span> { " by " } <a href="https://github.com/xycloo">{ "Xycloo" }</a></p>
                     <div>
         <input oninput={oninput} />
 
         </div>
         </div>
 
-        <pre><code class="language-json"> {&self.strkey
-        } </code></pre>
+        {
+            if let Some(error) = &self.error {
+                html! { <p class="error">{ error }</p> }
+            } else {
+                html! {}
+            }
+        }
+
+        <table>
+        {
+            for self.representations.iter().map(|(label, value)| html! {
+                <tr>
+                    <th>{ label }</th>
+                    <td><code>{ value }</code></td>
+                </tr>
+            })
+        }
+        </table>
+
+        <div id="derive">
+        <h2>{ "Predict a contract id" }</h2>
+        <p>{ "From the deploying account and salt:" }</p>
+        <input placeholder="G..." oninput={ondeployer} />
+        <input placeholder="salt (hex)" oninput={onsalt} />
+        {
+            if self.deployer.is_empty() && self.salt.is_empty() {
+                html! {}
+            } else {
+                derived_view(derived_from_deployer(&self.deployer, &self.salt))
+            }
+        }
+        <p>{ "From a Stellar asset:" }</p>
+        <input placeholder="native or CODE:ISSUER" oninput={onasset} />
+        {
+            if self.asset.is_empty() {
+                html! {}
+            } else {
+                derived_view(derived_from_asset(&self.asset))
+            }
+        }
+        </div>
         </main>
 
             }